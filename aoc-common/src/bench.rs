@@ -0,0 +1,194 @@
+use std::{
+    hint::black_box,
+    time::{Duration, Instant},
+};
+
+use crate::{Result, Solution};
+
+/// Timing statistics of the measured iterations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Stats {
+    pub min: Duration,
+    pub max: Duration,
+    pub mean: Duration,
+    pub median: Duration,
+    pub std_dev: Duration,
+}
+
+impl Stats {
+    /// Calculates the statistics, `samples` must not be empty.
+    pub fn from_samples(samples: &mut [Duration]) -> Self {
+        samples.sort();
+
+        let n = samples.len();
+        let mean = samples.iter().sum::<Duration>() / n as u32;
+        let median = if n.is_multiple_of(2) {
+            (samples[n / 2 - 1] + samples[n / 2]) / 2
+        } else {
+            samples[n / 2]
+        };
+        let variance = samples
+            .iter()
+            .map(|s| (s.as_secs_f64() - mean.as_secs_f64()).powi(2))
+            .sum::<f64>()
+            / n as f64;
+
+        Stats {
+            min: samples[0],
+            max: samples[n - 1],
+            mean,
+            median,
+            std_dev: Duration::from_secs_f64(variance.sqrt()),
+        }
+    }
+
+    fn to_json(self) -> String {
+        let ms = |d: Duration| d.as_secs_f64() * 1000.0;
+
+        format!(
+            "{{\"min_ms\":{:.6},\"median_ms\":{:.6},\"mean_ms\":{:.6},\"max_ms\":{:.6},\"std_dev_ms\":{:.6}}}",
+            ms(self.min),
+            ms(self.median),
+            ms(self.mean),
+            ms(self.max),
+            ms(self.std_dev)
+        )
+    }
+}
+
+/// Runs `f` for `warmup` iterations without measuring, then times every one of the following
+/// `iterations`. Stops at the first error.
+pub fn measure<T>(
+    iterations: usize,
+    warmup: usize,
+    mut f: impl FnMut() -> Result<T>,
+) -> Result<Stats> {
+    for _ in 0..warmup {
+        black_box(f()?);
+    }
+
+    let mut samples = Vec::with_capacity(iterations);
+    for _ in 0..iterations {
+        let started = Instant::now();
+        black_box(f()?);
+        samples.push(started.elapsed());
+    }
+
+    Ok(Stats::from_samples(&mut samples))
+}
+
+/// Timings of the separate stages of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Benchmark {
+    pub day: u8,
+    pub iterations: usize,
+    pub warmup: usize,
+    pub parse: Stats,
+    /// `None` if the part was not benchmarked.
+    pub part1: Option<Stats>,
+    /// `None` if the part was not benchmarked.
+    pub part2: Option<Stats>,
+}
+
+impl Benchmark {
+    /// Formats the timings as a table, one stage per row.
+    pub fn to_table(&self) -> String {
+        let mut table = format!(
+            "Day {}, {} iterations ({} warm-up)\n{:<8}{:>12}{:>12}{:>12}{:>12}{:>12}\n",
+            self.day, self.iterations, self.warmup, "", "min", "median", "mean", "max", "std dev"
+        );
+
+        let stages = [
+            ("parse", Some(self.parse)),
+            ("part 1", self.part1),
+            ("part 2", self.part2),
+        ];
+        for (stage, stats) in stages {
+            if let Some(s) = stats {
+                table += &format!(
+                    "{:<8}{:>12.2?}{:>12.2?}{:>12.2?}{:>12.2?}{:>12.2?}\n",
+                    stage, s.min, s.median, s.mean, s.max, s.std_dev
+                );
+            }
+        }
+
+        table
+    }
+
+    /// Formats the timings as a single-line JSON object, stages not benchmarked are `null`.
+    pub fn to_json(&self) -> String {
+        let stats = |s: Option<Stats>| s.map_or("null".to_string(), Stats::to_json);
+
+        format!(
+            "{{\"day\":{},\"iterations\":{},\"warmup\":{},\"parse\":{},\"part1\":{},\"part2\":{}}}",
+            self.day,
+            self.iterations,
+            self.warmup,
+            self.parse.to_json(),
+            stats(self.part1),
+            stats(self.part2)
+        )
+    }
+}
+
+/// Benchmarks parsing and the requested `part` of the solution (both if `None`) separately.
+pub fn bench<S: Solution>(
+    input: &str,
+    part: Option<u8>,
+    iterations: usize,
+    warmup: usize,
+) -> Result<Benchmark> {
+    let parse = measure(iterations, warmup, || S::parse(input))?;
+
+    let parsed = S::parse(input)?;
+    let part1 = match part {
+        Some(2) => None,
+        _ => Some(measure(iterations, warmup, || S::part1(&parsed))?),
+    };
+    let part2 = match part {
+        Some(1) => None,
+        _ => Some(measure(iterations, warmup, || S::part2(&parsed))?),
+    };
+
+    Ok(Benchmark {
+        day: S::DAY,
+        iterations,
+        warmup,
+        parse,
+        part1,
+        part2,
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::bench::{measure, Stats};
+
+    #[test]
+    fn from_samples_works() {
+        let mut samples = [4, 1, 3, 2].map(Duration::from_millis);
+
+        let stats = Stats::from_samples(&mut samples);
+
+        assert_eq!(stats.min, Duration::from_millis(1));
+        assert_eq!(stats.max, Duration::from_millis(4));
+        assert_eq!(stats.mean, Duration::from_micros(2500));
+        assert_eq!(stats.median, Duration::from_micros(2500));
+        assert_eq!(stats.std_dev.as_micros(), 1118);
+    }
+
+    #[test]
+    fn measure_runs_warmup_and_iterations() {
+        let mut runs = 0;
+
+        let stats = measure(5, 2, || {
+            runs += 1;
+            Ok(runs)
+        });
+
+        assert!(stats.is_ok());
+        assert_eq!(runs, 7);
+    }
+}
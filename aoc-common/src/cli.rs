@@ -0,0 +1,264 @@
+use std::{env, fmt, process::ExitCode, str::FromStr};
+
+use crate::{
+    input::InputSource,
+    report::{self, Report},
+    Result, Solution,
+};
+
+/// How the answers are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Format {
+    /// Human readable description of each answer.
+    #[default]
+    Text,
+    /// Single-line JSON object, see [`report::Report::to_json`].
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> std::result::Result<Self, Self::Err> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Format {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Format::Text => write!(f, "text"),
+            Format::Json => write!(f, "json"),
+        }
+    }
+}
+
+/// Day specific command line options, on top of the ones accepted by every day binary.
+#[derive(Debug, Default)]
+pub struct Options {
+    /// Options without a value, e.g. `--external`.
+    pub flags: &'static [&'static str],
+    /// Options followed by a value, e.g. `--chunk-size 1000`.
+    pub values: &'static [&'static str],
+    /// Synopsis of the options for the usage message.
+    pub usage: &'static str,
+}
+
+impl Options {
+    pub const NONE: Options = Options {
+        flags: &[],
+        values: &[],
+        usage: "",
+    };
+}
+
+/// Command line arguments of a day binary: `[input|-] [--format text|json]` followed by
+/// the day specific [`Options`].
+#[derive(Debug, Default, PartialEq)]
+pub struct Args {
+    pub input: Option<String>,
+    pub format: Format,
+    /// Day specific options in the order given, along with their values.
+    pub options: Vec<(String, Option<String>)>,
+}
+
+impl Args {
+    pub fn parse(
+        args: impl IntoIterator<Item = String>,
+        options: &Options,
+    ) -> std::result::Result<Self, String> {
+        let mut parsed = Args::default();
+
+        let mut args = args.into_iter();
+        while let Some(arg) = args.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = args.next().ok_or("--format requires a value.")?;
+                    parsed.format = value.parse()?;
+                }
+                flag if options.flags.contains(&flag) => parsed.options.push((arg, None)),
+                option if options.values.contains(&option) => {
+                    let value = args
+                        .next()
+                        .ok_or_else(|| format!("{} requires a value.", option))?;
+                    parsed.options.push((arg, Some(value)));
+                }
+                _ if arg.starts_with("--") || parsed.input.is_some() => {
+                    return Err(format!("Unexpected argument: {}", arg));
+                }
+                _ => parsed.input = Some(arg),
+            }
+        }
+
+        Ok(parsed)
+    }
+
+    /// Whether the day specific `flag` was given.
+    pub fn flag(&self, flag: &str) -> bool {
+        self.options.iter().any(|(name, _)| name == flag)
+    }
+
+    /// Parses the value of the day specific `option`, the last one wins if given repeatedly.
+    pub fn value<T>(&self, option: &str) -> std::result::Result<Option<T>, String>
+    where
+        T: FromStr,
+        T::Err: fmt::Display,
+    {
+        self.options
+            .iter()
+            .rev()
+            .find(|(name, _)| name == option)
+            .and_then(|(_, value)| value.as_deref())
+            .map(|value| {
+                value
+                    .parse()
+                    .map_err(|e| format!("Invalid {} '{}': {}", option, value, e))
+            })
+            .transpose()
+    }
+
+    /// Input selected on the command line, see [`InputSource::resolve`].
+    pub fn source(&self) -> InputSource {
+        InputSource::resolve(self.input.clone(), "input.txt")
+    }
+}
+
+/// Prints an invalid usage error along with the synopsis of the day binary.
+pub fn usage_error<S: Solution>(e: &str, options: &Options) -> ExitCode {
+    eprintln!("{}", e);
+    eprintln!(
+        "Usage: day{} [<path>|-] [--format text|json] {}",
+        S::DAY,
+        options.usage
+    );
+
+    ExitCode::FAILURE
+}
+
+/// Parses the command line of the day binary, printing the usage on failure.
+pub fn args<S: Solution>(options: &Options) -> std::result::Result<Args, ExitCode> {
+    Args::parse(env::args().skip(1), options).map_err(|e| usage_error::<S>(&e, options))
+}
+
+/// Prints the answers of the solution `S` in the requested format.
+pub fn print<S: Solution>(report: &Report, format: Format) {
+    match format {
+        Format::Text => {
+            if let Some(answer) = &report.part1 {
+                println!("{}: {}", S::PART1, answer);
+            }
+            if let Some(answer) = &report.part2 {
+                println!("{}: {}", S::PART2, answer);
+            }
+        }
+        Format::Json => println!("{}", report.to_json()),
+    }
+}
+
+/// Reads the whole input selected by `args` and passes it to `f`.
+///
+/// Errors are printed to stderr pointing at the offending input location and turned into
+/// a failure exit code.
+pub fn with_input(args: &Args, f: impl FnOnce(&str) -> Result<()>) -> ExitCode {
+    let source = args.source();
+
+    let result = source
+        .read_to_string()
+        .map_err(|e| (e, None))
+        .and_then(|input| f(&input).map_err(|e| (e, Some(input))));
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err((e, input)) => {
+            eprint!("{}", e.render(&source, input.as_deref()));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Passes the input source selected by `args` to `f`, for modes that read the input on their own,
+/// e.g. streaming it. Errors are printed to stderr and turned into a failure exit code.
+pub fn with_source(args: &Args, f: impl FnOnce(&InputSource) -> Result<()>) -> ExitCode {
+    let source = args.source();
+
+    match f(&source) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprint!("{}", e.render(&source, None));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+/// Solves both parts of `S` on the input selected by `args` and prints the answers.
+pub fn solve<S: Solution>(args: &Args) -> ExitCode {
+    with_input(args, |input| {
+        print::<S>(&report::solve::<S>(input, None)?, args.format);
+
+        Ok(())
+    })
+}
+
+/// Entry point shared by the day binaries without own options: reads the input selected on
+/// the command line, solves both parts and prints the answers.
+pub fn run<S: Solution>() -> ExitCode {
+    match args::<S>(&Options::NONE) {
+        Ok(args) => solve::<S>(&args),
+        Err(code) => code,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::cli::{Args, Format, Options};
+
+    const OPTIONS: Options = Options {
+        flags: &["--external"],
+        values: &["--chunk-size"],
+        usage: "[--external [--chunk-size <lines>]]",
+    };
+
+    fn args(s: &str) -> Result<Args, String> {
+        Args::parse(s.split_whitespace().map(String::from), &OPTIONS)
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!(args(""), Ok(Args::default()));
+        assert_eq!(
+            args("- --format json"),
+            Ok(Args {
+                input: Some("-".to_string()),
+                format: Format::Json,
+                options: vec![],
+            })
+        );
+    }
+
+    #[test]
+    fn parse_collects_options() {
+        let args = args("--external input.txt --chunk-size 10").unwrap();
+
+        assert_eq!(args.input, Some("input.txt".to_string()));
+        assert!(args.flag("--external"));
+        assert_eq!(args.value::<usize>("--chunk-size"), Ok(Some(10)));
+        assert_eq!(args.value::<usize>("--other"), Ok(None));
+    }
+
+    #[test]
+    fn parse_rejects_invalid() {
+        assert!(args("--format xml").is_err());
+        assert!(args("--format").is_err());
+        assert!(args("--chunk-size").is_err());
+        assert!(args("a.txt b.txt").is_err());
+        assert!(args("--verbose").is_err());
+        assert!(args("--chunk-size x")
+            .unwrap()
+            .value::<usize>("--chunk-size")
+            .is_err());
+    }
+}
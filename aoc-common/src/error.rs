@@ -0,0 +1,187 @@
+use std::{fmt, io};
+
+/// Errors that can happen while loading, parsing or solving a puzzle input.
+///
+/// Lines and columns are 1-based and counted in characters.
+#[derive(Debug)]
+pub enum Error {
+    /// Reading the input or writing a file or the output failed.
+    Io(io::Error),
+    /// Token is not a valid number.
+    InvalidNumber {
+        line: usize,
+        column: usize,
+        token: String,
+        reason: String,
+    },
+    /// Line ended before the expected token.
+    MissingToken {
+        line: usize,
+        column: usize,
+        expected: &'static str,
+    },
+    /// Token is not allowed at this position.
+    UnexpectedToken {
+        line: usize,
+        column: usize,
+        token: String,
+        expected: &'static str,
+    },
+    /// Line is well-formed but violates the puzzle constraints.
+    Invalid {
+        line: usize,
+        column: usize,
+        message: String,
+    },
+    /// Input can't be solved as a whole, e.g. a required element is missing.
+    Puzzle(String),
+    /// Calculated `quantity` does not fit the integer type of the given `width`.
+    Overflow {
+        quantity: &'static str,
+        width: &'static str,
+    },
+}
+
+pub type Result<T> = std::result::Result<T, Error>;
+
+impl Error {
+    /// Line and column in the input the error points to, if any.
+    pub fn position(&self) -> Option<(usize, usize)> {
+        match self {
+            Error::InvalidNumber { line, column, .. }
+            | Error::MissingToken { line, column, .. }
+            | Error::UnexpectedToken { line, column, .. }
+            | Error::Invalid { line, column, .. } => Some((*line, *column)),
+            Error::Io(_) | Error::Puzzle(_) | Error::Overflow { .. } => None,
+        }
+    }
+
+    /// Description of the error, without its position.
+    pub fn message(&self) -> String {
+        match self {
+            Error::Io(e) => format!("I/O error: {}", e),
+            Error::InvalidNumber { token, reason, .. } => {
+                format!("invalid number '{}': {}", token, reason)
+            }
+            Error::MissingToken { expected, .. } => format!("missing {}", expected),
+            Error::UnexpectedToken {
+                token, expected, ..
+            } => format!("unexpected '{}', expected {}", token, expected),
+            Error::Invalid { message, .. } | Error::Puzzle(message) => message.clone(),
+            Error::Overflow { quantity, width } => format!("{} overflows {}", quantity, width),
+        }
+    }
+
+    fn span(&self) -> usize {
+        match self {
+            Error::InvalidNumber { token, .. } | Error::UnexpectedToken { token, .. } => {
+                token.chars().count().max(1)
+            }
+            _ => 1,
+        }
+    }
+
+    /// Renders the error for the user, quoting the offending line of the `input` read from
+    /// `source` and pointing at the bad token, e.g.:
+    ///
+    /// ```text
+    /// error: invalid number '4x': invalid digit found in string
+    ///  --> input.txt:2:5
+    ///   |
+    /// 2 | 4   4x
+    ///   |     ^^
+    /// ```
+    pub fn render(&self, source: &impl fmt::Display, input: Option<&str>) -> String {
+        let mut rendered = format!("error: {}\n", self.message());
+
+        match self.position() {
+            Some((line, column)) => {
+                rendered += &format!(" --> {}:{}:{}\n", source, line, column);
+
+                if let Some(text) = input.and_then(|input| input.lines().nth(line - 1)) {
+                    let gutter = " ".repeat(line.to_string().len());
+                    rendered += &format!("{} |\n", gutter);
+                    rendered += &format!("{} | {}\n", line, text);
+                    rendered += &format!(
+                        "{} | {}{}\n",
+                        gutter,
+                        " ".repeat(column - 1),
+                        "^".repeat(self.span())
+                    );
+                }
+            }
+            None => rendered += &format!(" --> {}\n", source),
+        }
+
+        rendered
+    }
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.position() {
+            Some((line, column)) => write!(f, "{}:{}: {}", line, column, self.message()),
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io(e) => Some(e),
+            _ => None,
+        }
+    }
+}
+
+impl From<io::Error> for Error {
+    fn from(e: io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io;
+
+    use crate::Error;
+
+    #[test]
+    fn display_includes_position() {
+        let e = Error::MissingToken {
+            line: 3,
+            column: 5,
+            expected: "location ID",
+        };
+
+        assert_eq!(e.to_string(), "3:5: missing location ID");
+        assert_eq!(
+            Error::Puzzle("no guard".to_string()).to_string(),
+            "no guard"
+        );
+        assert_eq!(
+            Error::from(io::Error::from(io::ErrorKind::BrokenPipe)).to_string(),
+            "I/O error: broken pipe"
+        );
+    }
+
+    #[test]
+    fn render_points_at_token() {
+        let e = Error::InvalidNumber {
+            line: 2,
+            column: 5,
+            token: "4x".to_string(),
+            reason: "invalid digit found in string".to_string(),
+        };
+
+        assert_eq!(
+            e.render(&"input.txt", Some("3   4\n4   4x\n")),
+            "error: invalid number '4x': invalid digit found in string\n \
+             --> input.txt:2:5\n  \
+             |\n\
+             2 | 4   4x\n  \
+             |     ^^\n"
+        );
+    }
+}
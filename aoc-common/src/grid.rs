@@ -0,0 +1,34 @@
+/// Character grid as used by the map-like puzzles, indexed as `grid[row][column]`.
+pub type Grid = Vec<Vec<char>>;
+
+/// Parses a multiline string into a grid of characters, one row per line.
+pub fn parse_grid(s: &str) -> Grid {
+    s.lines().map(|line| line.chars().collect()).collect()
+}
+
+/// Finds `(row, column)` of the first cell matching the predicate, scanning row by row.
+pub fn find(grid: &[Vec<char>], predicate: impl Fn(char) -> bool) -> Option<(usize, usize)> {
+    grid.iter()
+        .enumerate()
+        .find_map(|(r, row)| row.iter().position(|&ch| predicate(ch)).map(|c| (r, c)))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::grid::{find, parse_grid};
+
+    #[test]
+    fn parse_grid_works() {
+        let grid = parse_grid("..#\n.^.\n");
+
+        assert_eq!(grid, vec![vec!['.', '.', '#'], vec!['.', '^', '.']]);
+    }
+
+    #[test]
+    fn find_works() {
+        let grid = parse_grid("..#\n.^.\n");
+
+        assert_eq!(find(&grid, |ch| ch == '^'), Some((1, 1)));
+        assert_eq!(find(&grid, |ch| ch == 'v'), None);
+    }
+}
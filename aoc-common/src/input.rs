@@ -0,0 +1,116 @@
+use std::{
+    env, fmt,
+    fs::File,
+    io::{self, BufRead, BufReader, Read},
+    path::{Path, PathBuf},
+};
+
+use crate::Result;
+
+/// Environment variable consulted for the input path when none is given explicitly.
+pub const INPUT_ENV_VAR: &str = "AOC_INPUT";
+
+/// Where the puzzle input is read from.
+#[derive(Debug, Clone, PartialEq)]
+pub enum InputSource {
+    Stdin,
+    File(PathBuf),
+}
+
+impl InputSource {
+    /// Picks the input from the command line argument if given, then from the `AOC_INPUT`
+    /// environment variable and finally falls back to the `default` file.
+    pub fn resolve(arg: Option<String>, default: impl Into<PathBuf>) -> Self {
+        Self::select(arg, env::var(INPUT_ENV_VAR).ok(), default)
+    }
+
+    fn select(arg: Option<String>, env_value: Option<String>, default: impl Into<PathBuf>) -> Self {
+        match arg.or(env_value) {
+            Some(value) => Self::from(value.as_str()),
+            None => Self::File(default.into()),
+        }
+    }
+
+    /// Opens the input for reading it line by line, without loading it into memory.
+    pub fn open(&self) -> Result<Box<dyn BufRead>> {
+        match self {
+            InputSource::Stdin => Ok(Box::new(io::stdin().lock())),
+            InputSource::File(path) => Ok(Box::new(BufReader::new(File::open(path)?))),
+        }
+    }
+
+    /// Reads the whole input into a string.
+    pub fn read_to_string(&self) -> Result<String> {
+        match self {
+            InputSource::Stdin => {
+                let mut content = String::new();
+                io::stdin().lock().read_to_string(&mut content)?;
+
+                Ok(content)
+            }
+            InputSource::File(path) => read_to_string(path),
+        }
+    }
+}
+
+impl From<&str> for InputSource {
+    /// `-` stands for the standard input, anything else is a file path.
+    fn from(value: &str) -> Self {
+        match value {
+            "-" => InputSource::Stdin,
+            path => InputSource::File(PathBuf::from(path)),
+        }
+    }
+}
+
+impl fmt::Display for InputSource {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            InputSource::Stdin => write!(f, "<stdin>"),
+            InputSource::File(path) => write!(f, "{}", path.display()),
+        }
+    }
+}
+
+/// Reads the whole input file into a string.
+pub fn read_to_string(path: impl AsRef<Path>) -> Result<String> {
+    let mut reader = BufReader::new(File::open(path)?);
+
+    let mut content = String::new();
+    reader.read_to_string(&mut content)?;
+
+    Ok(content)
+}
+
+#[cfg(test)]
+mod test {
+    use std::path::PathBuf;
+
+    use crate::input::InputSource;
+
+    #[test]
+    fn from_str_works() {
+        assert_eq!(InputSource::from("-"), InputSource::Stdin);
+        assert_eq!(
+            InputSource::from("day1/input.txt"),
+            InputSource::File(PathBuf::from("day1/input.txt"))
+        );
+    }
+
+    #[test]
+    fn select_prefers_argument_then_env() {
+        let select = |arg: Option<&str>, env: Option<&str>| {
+            InputSource::select(arg.map(String::from), env.map(String::from), "input.txt")
+        };
+
+        assert_eq!(select(Some("-"), Some("env.txt")), InputSource::Stdin);
+        assert_eq!(
+            select(None, Some("env.txt")),
+            InputSource::File(PathBuf::from("env.txt"))
+        );
+        assert_eq!(
+            select(None, None),
+            InputSource::File(PathBuf::from("input.txt"))
+        );
+    }
+}
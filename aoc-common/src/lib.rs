@@ -0,0 +1,15 @@
+//! Scaffolding shared by all the daily puzzles: input loading, parsing helpers,
+//! the common error type and the [`Solution`] trait implemented by every day.
+
+pub mod bench;
+pub mod cli;
+pub mod error;
+pub mod grid;
+pub mod input;
+pub mod parse;
+pub mod report;
+pub mod rng;
+pub mod solution;
+
+pub use error::{Error, Result};
+pub use solution::Solution;
@@ -0,0 +1,186 @@
+use std::{fmt::Display, str::FromStr};
+
+use crate::{Error, Result};
+
+/// Piece of an input line along with its 1-based column.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Token<'a> {
+    pub column: usize,
+    pub text: &'a str,
+}
+
+impl Token<'_> {
+    /// Parses the token as a number, reporting the `line` it comes from on failure.
+    pub fn parse<T>(&self, line: usize) -> Result<T>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        self.text.parse::<T>().map_err(|e| Error::InvalidNumber {
+            line,
+            column: self.column,
+            token: self.text.to_string(),
+            reason: e.to_string(),
+        })
+    }
+}
+
+/// Column right past the end of the line, where a missing token would be expected.
+pub fn end_column(line: &str) -> usize {
+    line.chars().count() + 1
+}
+
+fn token<'a>(line: &str, text: &'a str) -> Token<'a> {
+    // `text` is always a subslice of `line`, so the distance between them is its offset
+    let offset = text.as_ptr() as usize - line.as_ptr() as usize;
+
+    Token {
+        column: line[..offset].chars().count() + 1,
+        text,
+    }
+}
+
+/// Splits the line by whitespace, e.g. `"3   4"`.
+pub fn whitespace_tokens(line: &str) -> impl Iterator<Item = Token<'_>> {
+    line.split_whitespace().map(|text| token(line, text))
+}
+
+/// Splits the line by `separator`, e.g. `"75,47,61"` or `"47|53"`.
+pub fn separated_tokens(line: &str, separator: char) -> impl Iterator<Item = Token<'_>> {
+    line.split(separator).map(move |text| token(line, text))
+}
+
+/// Parses whitespace-separated numbers on the given (1-based) `line_no`.
+pub fn whitespace_separated<T>(line: &str, line_no: usize) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    whitespace_tokens(line).map(|t| t.parse(line_no)).collect()
+}
+
+/// Parses numbers separated by `separator` on the given (1-based) `line_no`.
+pub fn separated<T>(line: &str, line_no: usize, separator: char) -> Result<Vec<T>>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    separated_tokens(line, separator)
+        .map(|t| t.parse(line_no))
+        .collect()
+}
+
+/// Parses exactly `N` numbers out of the line tokens, e.g. a pair of location IDs.
+/// `expected` names a single number for error messages.
+pub fn exactly<'a, T, const N: usize>(
+    mut tokens: impl Iterator<Item = Token<'a>>,
+    line: &str,
+    line_no: usize,
+    expected: &'static str,
+) -> Result<[T; N]>
+where
+    T: FromStr + Copy + Default,
+    T::Err: Display,
+{
+    let mut values = [T::default(); N];
+    for value in values.iter_mut() {
+        *value = tokens
+            .next()
+            .ok_or(Error::MissingToken {
+                line: line_no,
+                column: end_column(line),
+                expected,
+            })?
+            .parse(line_no)?;
+    }
+
+    match tokens.next() {
+        Some(extra) => Err(Error::UnexpectedToken {
+            line: line_no,
+            column: extra.column,
+            token: extra.text.to_string(),
+            expected: "end of line",
+        }),
+        None => Ok(values),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        parse::{
+            exactly, separated, separated_tokens, whitespace_separated, whitespace_tokens, Token,
+        },
+        Error,
+    };
+
+    #[test]
+    fn whitespace_tokens_works() {
+        assert_eq!(
+            whitespace_tokens(" 3   4").collect::<Vec<_>>(),
+            vec![
+                Token {
+                    column: 2,
+                    text: "3"
+                },
+                Token {
+                    column: 6,
+                    text: "4"
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn whitespace_separated_works() {
+        assert_eq!(whitespace_separated::<u32>("3   4", 1).unwrap(), vec![3, 4]);
+        assert!(matches!(
+            whitespace_separated::<u32>("3 x", 7),
+            Err(Error::InvalidNumber {
+                line: 7,
+                column: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn separated_works() {
+        assert_eq!(
+            separated::<u32>("75,47,61", 1, ',').unwrap(),
+            vec![75, 47, 61]
+        );
+        assert_eq!(separated::<u32>("47|53", 1, '|').unwrap(), vec![47, 53]);
+        assert!(matches!(
+            separated::<u32>("47|", 2, '|'),
+            Err(Error::InvalidNumber {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn exactly_works() {
+        let pair = |line| exactly::<u32, 2>(separated_tokens(line, '|'), line, 1, "page");
+
+        assert_eq!(pair("47|53").unwrap(), [47, 53]);
+        assert!(matches!(
+            pair("47"),
+            Err(Error::MissingToken {
+                line: 1,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(
+            pair("47|53|61"),
+            Err(Error::UnexpectedToken {
+                line: 1,
+                column: 7,
+                ..
+            })
+        ));
+    }
+}
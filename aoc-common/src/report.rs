@@ -0,0 +1,138 @@
+use std::time::{Duration, Instant};
+
+use crate::{Result, Solution};
+
+/// Answers of a single run of a solution.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Report {
+    pub day: u8,
+    /// Answer of the first part, `None` if the part was not run.
+    pub part1: Option<String>,
+    /// Answer of the second part, `None` if the part was not run.
+    pub part2: Option<String>,
+    /// Time spent parsing the input and solving the parts.
+    pub elapsed: Duration,
+}
+
+impl Report {
+    /// Formats the report as a single-line JSON object, e.g.
+    /// `{"day":1,"part1":11,"part2":31,"elapsed_ms":0.042}`.
+    ///
+    /// Numeric answers are emitted as JSON numbers, anything else as strings.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"day\":{},\"part1\":{},\"part2\":{},\"elapsed_ms\":{:.3}}}",
+            self.day,
+            json_value(self.part1.as_deref()),
+            json_value(self.part2.as_deref()),
+            self.elapsed.as_secs_f64() * 1000.0
+        )
+    }
+}
+
+fn json_value(answer: Option<&str>) -> String {
+    match answer {
+        None => "null".to_string(),
+        Some(a) if is_json_integer(a) => a.to_string(),
+        Some(a) => json_string(a),
+    }
+}
+
+/// Whether the text is an integer as JSON writes it: an optional `-` followed by digits,
+/// without leading zeros. The shape is checked rather than parsed, so no width is too large.
+fn is_json_integer(text: &str) -> bool {
+    let digits = text.strip_prefix('-').unwrap_or(text);
+
+    !digits.is_empty()
+        && digits.bytes().all(|b| b.is_ascii_digit())
+        && (digits == "0" || !digits.starts_with('0'))
+}
+
+/// Quotes the text as a JSON string, escaping it as needed.
+pub fn json_string(text: &str) -> String {
+    let mut escaped = String::from("\"");
+    for ch in text.chars() {
+        match ch {
+            '"' => escaped += "\\\"",
+            '\\' => escaped += "\\\\",
+            '\n' => escaped += "\\n",
+            ch if ch.is_control() => escaped += &format!("\\u{:04x}", ch as u32),
+            ch => escaped.push(ch),
+        }
+    }
+    escaped.push('"');
+
+    escaped
+}
+
+/// Parses the input and runs the requested `part` of the solution, or both if `None`.
+pub fn solve<S: Solution>(input: &str, part: Option<u8>) -> Result<Report> {
+    let started = Instant::now();
+
+    let input = S::parse(input)?;
+
+    let part1 = match part {
+        Some(2) => None,
+        _ => Some(S::part1(&input)?.to_string()),
+    };
+    let part2 = match part {
+        Some(1) => None,
+        _ => Some(S::part2(&input)?.to_string()),
+    };
+
+    Ok(Report {
+        day: S::DAY,
+        part1,
+        part2,
+        elapsed: started.elapsed(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use crate::report::Report;
+
+    #[test]
+    fn to_json_works() {
+        let report = Report {
+            day: 5,
+            part1: Some("143".to_string()),
+            part2: None,
+            elapsed: Duration::from_micros(1500),
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":5,"part1":143,"part2":null,"elapsed_ms":1.500}"#
+        );
+    }
+
+    #[test]
+    fn to_json_quotes_non_numeric_answers() {
+        let report = Report {
+            day: 1,
+            part1: Some("a\"b".to_string()),
+            part2: Some("-7".to_string()),
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":1,"part1":"a\"b","part2":-7,"elapsed_ms":0.000}"#
+        );
+
+        let report = Report {
+            day: 1,
+            part1: Some(u128::MAX.to_string()),
+            part2: Some("007".to_string()),
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(
+            report.to_json(),
+            r#"{"day":1,"part1":340282366920938463463374607431768211455,"part2":"007","elapsed_ms":0.000}"#
+        );
+    }
+}
@@ -0,0 +1,89 @@
+//! Seeded pseudo-random numbers for generating reproducible inputs.
+
+use std::ops::RangeInclusive;
+
+/// SplitMix64 generator: tiny and fast, not suitable for anything but test data.
+#[derive(Debug, Clone)]
+pub struct Rng {
+    state: u64,
+}
+
+impl Rng {
+    /// Generator yielding the same sequence for the same `seed`.
+    pub fn new(seed: u64) -> Self {
+        Self { state: seed }
+    }
+
+    pub fn next_u64(&mut self) -> u64 {
+        self.state = self.state.wrapping_add(0x9e37_79b9_7f4a_7c15);
+
+        let mut z = self.state;
+        z = (z ^ (z >> 30)).wrapping_mul(0xbf58_476d_1ce4_e5b9);
+        z = (z ^ (z >> 27)).wrapping_mul(0x94d0_49bb_1331_11eb);
+        z ^ (z >> 31)
+    }
+
+    /// Number in `0..bound`, `bound` must be positive.
+    pub fn below(&mut self, bound: u64) -> u64 {
+        assert!(bound > 0, "bound must be positive");
+
+        // multiply-shift maps the full range onto the bound with negligible bias
+        ((self.next_u64() as u128 * bound as u128) >> 64) as u64
+    }
+
+    /// Number within the inclusive `range`, which must not be empty.
+    pub fn range(&mut self, range: RangeInclusive<u64>) -> u64 {
+        let (start, end) = range.into_inner();
+        assert!(start <= end, "range must not be empty");
+
+        match (end - start).checked_add(1) {
+            Some(span) => start + self.below(span),
+            None => self.next_u64(),
+        }
+    }
+
+    /// `true` with the given `probability`.
+    pub fn chance(&mut self, probability: f64) -> bool {
+        // 53 random bits make a uniformly distributed f64 in [0, 1)
+        ((self.next_u64() >> 11) as f64 / (1_u64 << 53) as f64) < probability
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::rng::Rng;
+
+    #[test]
+    fn same_seed_same_sequence() {
+        let (mut a, mut b) = (Rng::new(42), Rng::new(42));
+        let sequence: Vec<u64> = (0..10).map(|_| a.next_u64()).collect();
+
+        assert_eq!(sequence, (0..10).map(|_| b.next_u64()).collect::<Vec<_>>());
+        assert_ne!(
+            sequence,
+            (0..10).map(|_| Rng::new(43).next_u64()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn range_works() {
+        let mut rng = Rng::new(7);
+
+        for _ in 0..1000 {
+            assert!((10..=12).contains(&rng.range(10..=12)));
+        }
+        assert_eq!(rng.range(5..=5), 5);
+        rng.range(0..=u64::MAX);
+    }
+
+    #[test]
+    fn chance_works() {
+        let mut rng = Rng::new(7);
+
+        assert!(!(0..1000).any(|_| rng.chance(0.0)));
+        assert!((0..1000).all(|_| rng.chance(1.0)));
+
+        let hits = (0..10_000).filter(|_| rng.chance(0.25)).count();
+        assert!((2_000..3_000).contains(&hits));
+    }
+}
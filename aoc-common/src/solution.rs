@@ -0,0 +1,25 @@
+use std::fmt::Display;
+
+use crate::Result;
+
+/// Puzzle solution split into input parsing and the two independent parts.
+pub trait Solution {
+    /// Day of the puzzle in the advent calendar.
+    const DAY: u8;
+
+    /// Description of the first part answer used in the text output.
+    const PART1: &'static str = "Part 1";
+    /// Description of the second part answer used in the text output.
+    const PART2: &'static str = "Part 2";
+
+    /// Parsed puzzle input shared by both parts.
+    type Input;
+    type Answer1: Display;
+    type Answer2: Display;
+
+    fn parse(input: &str) -> Result<Self::Input>;
+
+    fn part1(input: &Self::Input) -> Result<Self::Answer1>;
+
+    fn part2(input: &Self::Input) -> Result<Self::Answer2>;
+}
@@ -0,0 +1,256 @@
+use std::{env, process::ExitCode};
+
+use aoc_common::{
+    bench::{self, Benchmark},
+    cli::Format,
+    input::InputSource,
+    report::{self, Report},
+};
+use day1::Day1;
+use day2::Day2;
+use day3::Day3;
+use day4::Day4;
+use day5::Day5;
+use day6::Day6;
+
+mod verify;
+
+const USAGE: &str = "Usage:
+  aoc run <day> [--part <1|2>] [--input <path|->] [--format <text|json>]
+                [--bench <iterations> [--warmup <iterations>]]
+  aoc verify [--answers <path>]
+
+Without --input, the AOC_INPUT environment variable or day<N>/input.txt is used.
+--bench times parsing and each part separately instead of printing the answers,
+warming up for a tenth of the iterations unless --warmup is given.
+verify checks the answers of every day listed in answers.toml.";
+
+const DAYS: std::ops::RangeInclusive<u8> = 1..=6;
+
+#[derive(Debug, PartialEq)]
+struct RunArgs {
+    day: u8,
+    part: Option<u8>,
+    input: Option<String>,
+    format: Format,
+    bench: Option<usize>,
+    warmup: Option<usize>,
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut args = args.iter();
+
+    let day = args
+        .next()
+        .ok_or("Day is not specified.")?
+        .parse::<u8>()
+        .map_err(|e| format!("Invalid day: {}", e))?;
+
+    if !DAYS.contains(&day) {
+        return Err(format!("Day {} is not solved yet.", day));
+    }
+
+    let mut part = None;
+    let mut input = None;
+    let mut format = Format::Text;
+    let mut bench = None;
+    let mut warmup = None;
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--part" => {
+                let value = args.next().ok_or("--part requires a value.")?;
+                part = match value.as_str() {
+                    "1" => Some(1),
+                    "2" => Some(2),
+                    _ => return Err(format!("Invalid part: {}", value)),
+                };
+            }
+            "--input" => {
+                let value = args.next().ok_or("--input requires a value.")?;
+                input = Some(value.clone());
+            }
+            "--format" => {
+                let value = args.next().ok_or("--format requires a value.")?;
+                format = value.parse()?;
+            }
+            "--bench" => {
+                let value = args.next().ok_or("--bench requires a value.")?;
+                bench = match value.parse::<usize>() {
+                    Ok(iterations) if iterations > 0 => Some(iterations),
+                    _ => return Err(format!("Invalid number of iterations: {}", value)),
+                };
+            }
+            "--warmup" => {
+                let value = args.next().ok_or("--warmup requires a value.")?;
+                warmup = Some(
+                    value
+                        .parse::<usize>()
+                        .map_err(|e| format!("Invalid number of warm-up iterations: {}", e))?,
+                );
+            }
+            _ => return Err(format!("Unexpected argument: {}", arg)),
+        }
+    }
+
+    if warmup.is_some() && bench.is_none() {
+        return Err("--warmup requires --bench.".to_string());
+    }
+
+    Ok(RunArgs {
+        day,
+        part,
+        input,
+        format,
+        bench,
+        warmup,
+    })
+}
+
+fn solve_day(day: u8, input: &str, part: Option<u8>) -> aoc_common::Result<Report> {
+    match day {
+        1 => report::solve::<Day1>(input, part),
+        2 => report::solve::<Day2>(input, part),
+        3 => report::solve::<Day3>(input, part),
+        4 => report::solve::<Day4>(input, part),
+        5 => report::solve::<Day5>(input, part),
+        6 => report::solve::<Day6>(input, part),
+        _ => unreachable!("Day {} is not solved yet.", day),
+    }
+}
+
+fn bench_day(
+    day: u8,
+    input: &str,
+    part: Option<u8>,
+    iterations: usize,
+    warmup: usize,
+) -> aoc_common::Result<Benchmark> {
+    match day {
+        1 => bench::bench::<Day1>(input, part, iterations, warmup),
+        2 => bench::bench::<Day2>(input, part, iterations, warmup),
+        3 => bench::bench::<Day3>(input, part, iterations, warmup),
+        4 => bench::bench::<Day4>(input, part, iterations, warmup),
+        5 => bench::bench::<Day5>(input, part, iterations, warmup),
+        6 => bench::bench::<Day6>(input, part, iterations, warmup),
+        _ => unreachable!("Day {} is not solved yet.", day),
+    }
+}
+
+fn run(args: &RunArgs) -> Result<(), String> {
+    let source = InputSource::resolve(args.input.clone(), format!("day{}/input.txt", args.day));
+    let input = source
+        .read_to_string()
+        .map_err(|e| e.render(&source, None))?;
+
+    if let Some(iterations) = args.bench {
+        let warmup = args.warmup.unwrap_or(iterations.div_ceil(10));
+        let benchmark = bench_day(args.day, &input, args.part, iterations, warmup)
+            .map_err(|e| e.render(&source, Some(&input)))?;
+
+        match args.format {
+            Format::Text => print!("{}", benchmark.to_table()),
+            Format::Json => println!("{}", benchmark.to_json()),
+        }
+
+        return Ok(());
+    }
+
+    let report =
+        solve_day(args.day, &input, args.part).map_err(|e| e.render(&source, Some(&input)))?;
+
+    match args.format {
+        Format::Text => {
+            if let Some(answer) = report.part1 {
+                println!("Day {}, part 1: {}", args.day, answer);
+            }
+            if let Some(answer) = report.part2 {
+                println!("Day {}, part 2: {}", args.day, answer);
+            }
+        }
+        Format::Json => println!("{}", report.to_json()),
+    }
+
+    Ok(())
+}
+
+fn main() -> ExitCode {
+    let args: Vec<String> = env::args().skip(1).collect();
+
+    let result = match args.first().map(String::as_str) {
+        Some("run") => parse_run_args(&args[1..]).and_then(|run_args| run(&run_args)),
+        Some("verify") => verify::verify(&args[1..]),
+        _ => Err(USAGE.to_string()),
+    };
+
+    match result {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.trim_end());
+            ExitCode::FAILURE
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::cli::Format;
+
+    use crate::{parse_run_args, RunArgs};
+
+    fn args(s: &str) -> Vec<String> {
+        s.split_whitespace().map(String::from).collect()
+    }
+
+    #[test]
+    fn parse_run_args_works() {
+        assert_eq!(
+            parse_run_args(&args("5 --part 2 --input path/to/input.txt --format json")),
+            Ok(RunArgs {
+                day: 5,
+                part: Some(2),
+                input: Some("path/to/input.txt".to_string()),
+                format: Format::Json,
+                bench: None,
+                warmup: None,
+            })
+        );
+        assert_eq!(
+            parse_run_args(&args("3")),
+            Ok(RunArgs {
+                day: 3,
+                part: None,
+                input: None,
+                format: Format::Text,
+                bench: None,
+                warmup: None,
+            })
+        );
+    }
+
+    #[test]
+    fn parse_run_args_with_bench_works() {
+        assert_eq!(
+            parse_run_args(&args("6 --bench 100 --warmup 5")),
+            Ok(RunArgs {
+                day: 6,
+                part: None,
+                input: None,
+                format: Format::Text,
+                bench: Some(100),
+                warmup: Some(5),
+            })
+        );
+    }
+
+    #[test]
+    fn parse_run_args_rejects_invalid() {
+        assert!(parse_run_args(&args("")).is_err());
+        assert!(parse_run_args(&args("26")).is_err());
+        assert!(parse_run_args(&args("1 --part 3")).is_err());
+        assert!(parse_run_args(&args("1 --input")).is_err());
+        assert!(parse_run_args(&args("1 --verbose")).is_err());
+        assert!(parse_run_args(&args("1 --format yaml")).is_err());
+        assert!(parse_run_args(&args("1 --bench 0")).is_err());
+        assert!(parse_run_args(&args("1 --warmup 5")).is_err());
+    }
+}
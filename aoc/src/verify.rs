@@ -0,0 +1,234 @@
+use aoc_common::{input, report::Report};
+use toml::{Table, Value};
+
+use crate::{solve_day, DAYS};
+
+/// Expected answers of a single day, read from a table like:
+///
+/// ```toml
+/// [day5]
+/// input = "day5/input.txt" # optional, defaults to day<N>/input.txt
+/// part1 = 143
+/// part2 = 123
+/// ```
+#[derive(Debug, PartialEq)]
+pub struct Expected {
+    pub day: u8,
+    pub input: Option<String>,
+    pub part1: Option<String>,
+    pub part2: Option<String>,
+}
+
+/// Difference between the expected and the actual answer.
+#[derive(Debug, PartialEq)]
+pub struct Mismatch {
+    pub part: u8,
+    pub expected: String,
+    pub actual: String,
+}
+
+fn answer(table: &Table, key: &str, day_key: &str) -> Result<Option<String>, String> {
+    match table.get(key) {
+        None => Ok(None),
+        Some(Value::Integer(i)) => Ok(Some(i.to_string())),
+        Some(Value::String(s)) => Ok(Some(s.clone())),
+        Some(v) => Err(format!(
+            "{}.{} must be an integer or a string, got {}",
+            day_key,
+            key,
+            v.type_str()
+        )),
+    }
+}
+
+pub fn parse_answers(content: &str) -> Result<Vec<Expected>, String> {
+    let table: Table = content
+        .parse()
+        .map_err(|e| format!("Failed to parse answers: {}", e))?;
+
+    let mut answers = Vec::new();
+    for (key, value) in &table {
+        let day = key
+            .strip_prefix("day")
+            .and_then(|n| n.parse::<u8>().ok())
+            .filter(|day| DAYS.contains(day))
+            .ok_or_else(|| format!("Unexpected day in answers: {}", key))?;
+
+        let day_table = value
+            .as_table()
+            .ok_or_else(|| format!("{} must be a table", key))?;
+
+        if let Some(unknown) = day_table
+            .keys()
+            .find(|k| !["input", "part1", "part2"].contains(&k.as_str()))
+        {
+            return Err(format!("Unexpected key in answers: {}.{}", key, unknown));
+        }
+
+        answers.push(Expected {
+            day,
+            input: answer(day_table, "input", key)?,
+            part1: answer(day_table, "part1", key)?,
+            part2: answer(day_table, "part2", key)?,
+        });
+    }
+
+    answers.sort_by_key(|expected| expected.day);
+
+    Ok(answers)
+}
+
+/// Compares answers present in `expected` with the ones in the `report`.
+pub fn compare(expected: &Expected, report: &Report) -> Vec<Mismatch> {
+    [
+        (1, &expected.part1, &report.part1),
+        (2, &expected.part2, &report.part2),
+    ]
+    .into_iter()
+    .filter_map(|(part, expected, actual)| match (expected, actual) {
+        (Some(expected), Some(actual)) if expected != actual => Some(Mismatch {
+            part,
+            expected: expected.clone(),
+            actual: actual.clone(),
+        }),
+        _ => None,
+    })
+    .collect()
+}
+
+/// Runs every day listed in the answers file and checks its answers.
+///
+/// Fails if any of the days has a mismatching answer or could not be solved.
+pub fn verify(args: &[String]) -> Result<(), String> {
+    let answers_path = match args {
+        [] => "answers.toml",
+        [flag, path] if flag == "--answers" => path.as_str(),
+        _ => return Err(format!("Unexpected arguments: {}", args.join(" "))),
+    };
+
+    let content = input::read_to_string(answers_path).map_err(|e| e.render(&answers_path, None))?;
+
+    let mut failed = 0;
+    let answers = parse_answers(&content)?;
+    for expected in &answers {
+        let source = expected
+            .input
+            .clone()
+            .unwrap_or_else(|| format!("day{}/input.txt", expected.day));
+
+        let result = input::read_to_string(&source)
+            .map_err(|e| e.render(&source, None))
+            .and_then(|input| {
+                solve_day(expected.day, &input, None).map_err(|e| e.render(&source, Some(&input)))
+            });
+
+        match result {
+            Ok(report) => {
+                let mismatches = compare(expected, &report);
+                if mismatches.is_empty() {
+                    println!("Day {}: ok", expected.day);
+                    continue;
+                }
+
+                println!("Day {}: MISMATCH", expected.day);
+                for mismatch in mismatches {
+                    println!("  part {}:", mismatch.part);
+                    println!("  - {}", mismatch.expected);
+                    println!("  + {}", mismatch.actual);
+                }
+            }
+            Err(e) => {
+                println!("Day {}: FAILED", expected.day);
+                eprint!("{}", e);
+            }
+        }
+
+        failed += 1;
+    }
+
+    if failed > 0 {
+        return Err(format!(
+            "{} of {} days failed verification.",
+            failed,
+            answers.len()
+        ));
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod test {
+    use std::time::Duration;
+
+    use aoc_common::report::Report;
+
+    use crate::verify::{compare, parse_answers, Expected, Mismatch};
+
+    #[test]
+    fn parse_answers_works() {
+        let answers = parse_answers(
+            r#"
+            [day5]
+            part1 = 143
+
+            [day1]
+            input = "examples/day1.txt"
+            part1 = 11
+            part2 = "31"
+            "#,
+        );
+
+        assert_eq!(
+            answers,
+            Ok(vec![
+                Expected {
+                    day: 1,
+                    input: Some("examples/day1.txt".to_string()),
+                    part1: Some("11".to_string()),
+                    part2: Some("31".to_string()),
+                },
+                Expected {
+                    day: 5,
+                    input: None,
+                    part1: Some("143".to_string()),
+                    part2: None,
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn parse_answers_rejects_invalid() {
+        assert!(parse_answers("[day26]\npart1 = 1").is_err());
+        assert!(parse_answers("[dayX]\npart1 = 1").is_err());
+        assert!(parse_answers("day1 = 1").is_err());
+        assert!(parse_answers("[day1]\npart3 = 1").is_err());
+        assert!(parse_answers("[day1]\npart1 = 1.5").is_err());
+    }
+
+    #[test]
+    fn compare_works() {
+        let expected = Expected {
+            day: 1,
+            input: None,
+            part1: Some("11".to_string()),
+            part2: Some("31".to_string()),
+        };
+        let report = Report {
+            day: 1,
+            part1: Some("11".to_string()),
+            part2: Some("32".to_string()),
+            elapsed: Duration::ZERO,
+        };
+
+        assert_eq!(
+            compare(&expected, &report),
+            vec![Mismatch {
+                part: 2,
+                expected: "31".to_string(),
+                actual: "32".to_string(),
+            }]
+        );
+    }
+}
@@ -0,0 +1,38 @@
+//! Times day 1 on large generated lists, run once with and once without the `parallel` feature
+//! to see the speedup:
+//!
+//! ```sh
+//! cargo bench -p day1
+//! cargo bench -p day1 --features parallel
+//! ```
+
+use aoc_common::bench;
+use day1::{generate::Generator, Day1Of};
+
+const ROWS: [usize; 2] = [100_000, 1_000_000];
+const ITERATIONS: usize = 10;
+const WARMUP: usize = 2;
+
+fn main() -> aoc_common::Result<()> {
+    let mode = if cfg!(feature = "parallel") {
+        "parallel"
+    } else {
+        "sequential"
+    };
+
+    for rows in ROWS {
+        // the similarity score of that many rows overflows `u32`, hence comparing them as `u64`
+        let input = Generator {
+            rows,
+            ..Generator::default()
+        }
+        .generate();
+        let benchmark = bench::bench::<Day1Of<u64>>(&input, None, ITERATIONS, WARMUP)?;
+
+        println!("{} rows, {}", rows, mode);
+        print!("{}", benchmark.to_table());
+        println!();
+    }
+
+    Ok(())
+}
@@ -0,0 +1,186 @@
+//! Breakdown of the answers into the contributions of the sorted pairs.
+
+use crate::{
+    distance, group,
+    location::{checked, LocationId},
+    similarity, LocationLists,
+};
+
+/// Number of top contributors listed unless asked otherwise.
+pub const DEFAULT_TOP: usize = 5;
+
+/// Pair of location IDs at the same position of the sorted lists.
+#[derive(Debug, PartialEq)]
+pub struct Pair<T = u32> {
+    pub left: T,
+    pub right: T,
+    /// Contribution to the total distance.
+    pub distance: T,
+    /// Contribution of the left ID to the similarity score.
+    pub similarity: T,
+}
+
+/// Contributions of every sorted pair to the answers, along with the answers themselves.
+pub struct Explanation<T = u32> {
+    pub pairs: Vec<Pair<T>>,
+    pub distance: T,
+    pub similarity: T,
+}
+
+impl<T: LocationId> Explanation<T> {
+    pub fn new(lists: &LocationLists<T>) -> aoc_common::Result<Self> {
+        let (list1, list2) = (lists.list1(), lists.list2());
+        let list2_groupped = group(list2);
+
+        let pairs = list1
+            .iter()
+            .zip(list2.iter())
+            .map(|(&left, &right)| {
+                Ok(Pair {
+                    left,
+                    right,
+                    distance: checked(left.distance(right), "distance")?,
+                    similarity: similarity(&[left], &list2_groupped)?,
+                })
+            })
+            .collect::<aoc_common::Result<_>>()?;
+
+        Ok(Self {
+            pairs,
+            distance: distance(list1, list2)?,
+            similarity: similarity(list1, &list2_groupped)?,
+        })
+    }
+
+    /// Indices of the `top` pairs contributing most to the total distance, largest first.
+    /// Pairs not contributing at all are left out.
+    pub fn top_distance(&self, top: usize) -> Vec<usize> {
+        self.top_by(top, |pair| pair.distance)
+    }
+
+    /// Indices of the `top` pairs contributing most to the similarity score, largest first.
+    /// Pairs not contributing at all are left out.
+    pub fn top_similarity(&self, top: usize) -> Vec<usize> {
+        self.top_by(top, |pair| pair.similarity)
+    }
+
+    fn top_by(&self, top: usize, contribution: impl Fn(&Pair<T>) -> T) -> Vec<usize> {
+        let mut indices: Vec<usize> = (0..self.pairs.len())
+            .filter(|&idx| contribution(&self.pairs[idx]) != T::default())
+            .collect();
+        // stable, so equal contributions keep the order of the pairs
+        indices.sort_by(|&a, &b| contribution(&self.pairs[b]).cmp(&contribution(&self.pairs[a])));
+        indices.truncate(top);
+
+        indices
+    }
+
+    /// Formats the pairs followed by the totals and the `top` contributors to each of them.
+    pub fn to_table(&self, top: usize) -> String {
+        let row = |idx: usize| {
+            let pair = &self.pairs[idx];
+            format!(
+                "{:>8}{:>12}{:>12}{:>12}{:>12}\n",
+                idx + 1,
+                pair.left,
+                pair.right,
+                pair.distance,
+                pair.similarity
+            )
+        };
+        let header = format!(
+            "{:>8}{:>12}{:>12}{:>12}{:>12}\n",
+            "pair", "left", "right", "distance", "similarity"
+        );
+
+        let mut table = header.clone();
+        for idx in 0..self.pairs.len() {
+            table += &row(idx);
+        }
+        table += &format!(
+            "{:>8}{:>12}{:>12}{:>12}{:>12}\n",
+            "total", "", "", self.distance, self.similarity
+        );
+
+        for (title, indices) in [
+            ("distance", self.top_distance(top)),
+            ("similarity", self.top_similarity(top)),
+        ] {
+            table += &format!("\nTop {} by {}\n", top, title);
+            table += &header;
+            for idx in indices {
+                table += &row(idx);
+            }
+        }
+
+        table
+    }
+
+    /// Formats the pairs as CSV, ranking the `top` contributors to each total in the last columns.
+    pub fn to_csv(&self, top: usize) -> String {
+        let rank = |indices: &[usize], idx: usize| {
+            indices
+                .iter()
+                .position(|&i| i == idx)
+                .map_or(String::new(), |rank| (rank + 1).to_string())
+        };
+        let (top_distance, top_similarity) = (self.top_distance(top), self.top_similarity(top));
+
+        let mut csv =
+            "pair,left,right,distance,similarity,distance_rank,similarity_rank\n".to_string();
+        for (idx, pair) in self.pairs.iter().enumerate() {
+            csv += &format!(
+                "{},{},{},{},{},{},{}\n",
+                idx + 1,
+                pair.left,
+                pair.right,
+                pair.distance,
+                pair.similarity,
+                rank(&top_distance, idx),
+                rank(&top_similarity, idx)
+            );
+        }
+
+        csv
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::Solution;
+
+    use crate::{explain::Explanation, Day1};
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn explanation_works() {
+        let explanation = Explanation::new(&Day1::parse(EXAMPLE).unwrap()).unwrap();
+
+        assert_eq!(explanation.pairs.len(), 6);
+        assert_eq!(
+            explanation.pairs.iter().map(|p| p.distance).sum::<u32>(),
+            explanation.distance
+        );
+        assert_eq!(
+            explanation.pairs.iter().map(|p| p.similarity).sum::<u32>(),
+            explanation.similarity
+        );
+        assert_eq!((explanation.distance, explanation.similarity), (11, 31));
+
+        assert_eq!(explanation.top_distance(2), vec![5, 0]);
+        assert_eq!(explanation.top_similarity(5), vec![2, 3, 4, 5]);
+    }
+
+    #[test]
+    fn to_csv_works() {
+        let explanation = Explanation::new(&Day1::parse("1   1\n5   1\n").unwrap()).unwrap();
+
+        assert_eq!(
+            explanation.to_csv(1),
+            "pair,left,right,distance,similarity,distance_rank,similarity_rank\n\
+             1,1,1,0,2,,1\n\
+             2,5,1,4,0,1,\n"
+        );
+    }
+}
@@ -0,0 +1,384 @@
+//! Streaming comparison of location lists that don't fit into memory.
+//!
+//! Both columns are read in chunks, each chunk is sorted and spilled to a temporary file as a
+//! sorted run. The runs of each column are then merged back into a single sorted stream, a few
+//! of them at a time so that the number of open files stays bounded. The merged streams give the
+//! distance, along with the counts of equal IDs the similarity is computed from.
+
+use std::{
+    cmp::Reverse,
+    collections::BinaryHeap,
+    env, fmt,
+    fs::{self, File},
+    io::{BufRead, BufReader, BufWriter, Lines, Write},
+    mem,
+    path::{Path, PathBuf},
+    process,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+use aoc_common::{parse, Error};
+
+use crate::location::{checked, LocationId};
+
+/// Default number of lines sorted in memory at once.
+pub const DEFAULT_CHUNK_SIZE: usize = 1_000_000;
+
+/// Most runs merged at once. Both columns are merged side by side, so twice as many files are
+/// open, well below the common limit of 1024.
+const MAX_FAN_IN: usize = 128;
+
+/// Most lines reserved for a chunk up front, larger chunks grow as the lines arrive.
+const MAX_INITIAL_CAPACITY: usize = 64 * 1024;
+
+static RUN_COUNTER: AtomicUsize = AtomicUsize::new(0);
+
+/// Sorted runs of a single column, removed from the disk when dropped.
+struct Runs {
+    paths: Vec<PathBuf>,
+}
+
+impl Runs {
+    fn new() -> Self {
+        Self { paths: Vec::new() }
+    }
+
+    /// Creates the file of a new run.
+    fn create(&mut self) -> aoc_common::Result<BufWriter<File>> {
+        let path = env::temp_dir().join(format!(
+            "day1-run-{}-{}.txt",
+            process::id(),
+            RUN_COUNTER.fetch_add(1, Ordering::Relaxed)
+        ));
+        self.paths.push(path.clone());
+
+        Ok(BufWriter::new(File::create(path)?))
+    }
+
+    /// Sorts the chunk and writes it as a new run.
+    fn spill<T: LocationId>(&mut self, chunk: &mut Vec<T>) -> aoc_common::Result<()> {
+        chunk.sort_unstable();
+
+        let mut writer = self.create()?;
+        for id in chunk.drain(..) {
+            writeln!(writer, "{}", id)?;
+        }
+        writer.flush()?;
+
+        Ok(())
+    }
+
+    /// Merges the runs `fan_in` at a time into longer ones, until at most `fan_in` are left.
+    fn compact<T: LocationId>(&mut self, fan_in: usize) -> aoc_common::Result<()> {
+        while self.paths.len() > fan_in {
+            let mut compacted = Runs::new();
+            for paths in self.paths.chunks(fan_in) {
+                let mut writer = compacted.create()?;
+                for id in MergedRuns::<T>::open(paths)? {
+                    writeln!(writer, "{}", id?)?;
+                }
+                writer.flush()?;
+            }
+
+            // the merged runs are removed along with `compacted`
+            mem::swap(&mut self.paths, &mut compacted.paths);
+        }
+
+        Ok(())
+    }
+
+    /// Merges all runs into a single sorted stream.
+    fn merged<T: LocationId>(&self) -> aoc_common::Result<MergedRuns<T>> {
+        MergedRuns::open(&self.paths)
+    }
+}
+
+impl Drop for Runs {
+    fn drop(&mut self) {
+        for path in &self.paths {
+            let _ = fs::remove_file(path);
+        }
+    }
+}
+
+/// K-way merge of sorted runs, keeping the smallest head of every run in a min-heap.
+struct MergedRuns<T> {
+    runs: Vec<Lines<BufReader<File>>>,
+    heap: BinaryHeap<Reverse<(T, usize)>>,
+}
+
+impl<T: LocationId> MergedRuns<T> {
+    fn open(paths: &[PathBuf]) -> aoc_common::Result<Self> {
+        let mut runs = Vec::with_capacity(paths.len());
+        for path in paths {
+            runs.push(BufReader::new(File::open(path)?).lines());
+        }
+
+        let mut merged = MergedRuns {
+            runs,
+            heap: BinaryHeap::new(),
+        };
+        for idx in 0..merged.runs.len() {
+            merged.advance(idx)?;
+        }
+
+        Ok(merged)
+    }
+
+    fn advance(&mut self, idx: usize) -> aoc_common::Result<()> {
+        if let Some(line) = self.runs[idx].next() {
+            let id = line?
+                .parse()
+                .map_err(|e| Error::Puzzle(format!("corrupted sorted run: {}", e)))?;
+            self.heap.push(Reverse((id, idx)));
+        }
+
+        Ok(())
+    }
+}
+
+impl<T: LocationId> Iterator for MergedRuns<T> {
+    type Item = aoc_common::Result<T>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let Reverse((id, idx)) = self.heap.pop()?;
+
+        Some(self.advance(idx).map(|_| id))
+    }
+}
+
+/// Writes the counts of equal IDs of a sorted stream as `id count` lines.
+struct GroupWriter<T> {
+    writer: BufWriter<File>,
+    group: Option<(T, usize)>,
+}
+
+impl<T: LocationId> GroupWriter<T> {
+    fn new(writer: BufWriter<File>) -> Self {
+        Self {
+            writer,
+            group: None,
+        }
+    }
+
+    fn push(&mut self, id: T) -> aoc_common::Result<()> {
+        match &mut self.group {
+            Some((group, count)) if *group == id => *count += 1,
+            _ => {
+                self.write_group()?;
+                self.group = Some((id, 1));
+            }
+        }
+
+        Ok(())
+    }
+
+    fn write_group(&mut self) -> aoc_common::Result<()> {
+        if let Some((id, count)) = self.group.take() {
+            writeln!(self.writer, "{} {}", id, count)?;
+        }
+
+        Ok(())
+    }
+
+    fn finish(mut self) -> aoc_common::Result<()> {
+        self.write_group()?;
+        self.writer.flush()?;
+
+        Ok(())
+    }
+}
+
+/// Reads the `(id, count)` groups written by [`GroupWriter`].
+fn read_groups<T: LocationId>(
+    path: &Path,
+) -> aoc_common::Result<impl Iterator<Item = aoc_common::Result<(T, usize)>>> {
+    let corrupted = |e: &dyn fmt::Display| Error::Puzzle(format!("corrupted groups: {}", e));
+
+    Ok(BufReader::new(File::open(path)?).lines().map(move |line| {
+        let line = line?;
+        let (id, count) = line.split_once(' ').ok_or_else(|| corrupted(&line))?;
+
+        Ok((
+            id.parse().map_err(|e| corrupted(&e))?,
+            count.parse().map_err(|e| corrupted(&e))?,
+        ))
+    }))
+}
+
+fn next_group<T: LocationId>(
+    groups: &mut impl Iterator<Item = aoc_common::Result<(T, usize)>>,
+) -> aoc_common::Result<Option<(T, usize)>> {
+    groups.next().transpose()
+}
+
+/// Compares the lists read line by line from `reader`, keeping at most `chunk_size` lines
+/// in memory. Returns the total distance and the similarity score.
+pub fn compare_lists<T: LocationId>(
+    reader: impl BufRead,
+    chunk_size: usize,
+) -> aoc_common::Result<(T, T)> {
+    compare(reader, chunk_size, MAX_FAN_IN)
+}
+
+fn compare<T: LocationId>(
+    reader: impl BufRead,
+    chunk_size: usize,
+    fan_in: usize,
+) -> aoc_common::Result<(T, T)> {
+    let mut runs1 = Runs::new();
+    let mut runs2 = Runs::new();
+    let capacity = chunk_size.min(MAX_INITIAL_CAPACITY);
+    let mut chunk1: Vec<T> = Vec::with_capacity(capacity);
+    let mut chunk2: Vec<T> = Vec::with_capacity(capacity);
+
+    for (idx, line) in reader.lines().enumerate() {
+        let line = line?;
+        let [l1n, l2n] = parse::exactly(
+            parse::whitespace_tokens(&line),
+            &line,
+            idx + 1,
+            "location ID",
+        )?;
+
+        chunk1.push(l1n);
+        chunk2.push(l2n);
+
+        if chunk1.len() >= chunk_size {
+            runs1.spill(&mut chunk1)?;
+            runs2.spill(&mut chunk2)?;
+        }
+    }
+
+    if !chunk1.is_empty() {
+        runs1.spill(&mut chunk1)?;
+        runs2.spill(&mut chunk2)?;
+    }
+
+    runs1.compact::<T>(fan_in)?;
+    runs2.compact::<T>(fan_in)?;
+
+    // single merge of both columns: the sorted IDs are paired up for the distance, while the
+    // counts of equal IDs are set aside for the similarity
+    let mut groups = Runs::new();
+    let mut writer1 = GroupWriter::new(groups.create()?);
+    let mut writer2 = GroupWriter::new(groups.create()?);
+    let mut distance = T::default();
+    for (l1n, l2n) in runs1.merged::<T>()?.zip(runs2.merged::<T>()?) {
+        let (l1n, l2n) = (l1n?, l2n?);
+        writer1.push(l1n)?;
+        writer2.push(l2n)?;
+
+        let dist = checked(l1n.distance(l2n), "distance")?;
+        distance = checked(distance.checked_add(dist), "distance")?;
+    }
+    writer1.finish()?;
+    writer2.finish()?;
+    drop((runs1, runs2));
+
+    // both group streams are sorted, so equal IDs can be matched the same way as in a merge join
+    let mut similarity = T::default();
+    let mut groups1 = read_groups::<T>(&groups.paths[0])?;
+    let mut groups2 = read_groups::<T>(&groups.paths[1])?;
+    let mut group2 = next_group(&mut groups2)?;
+    while let Some((l1n, count1)) = next_group(&mut groups1)? {
+        while matches!(group2, Some((l2n, _)) if l2n < l1n) {
+            group2 = next_group(&mut groups2)?;
+        }
+
+        if let Some((l2n, count2)) = group2 {
+            if l2n == l1n {
+                let sim = count1
+                    .checked_mul(count2)
+                    .and_then(T::from_count)
+                    .and_then(|q| l1n.checked_mul(q));
+                similarity = checked(
+                    similarity.checked_add(checked(sim, "similarity")?),
+                    "similarity",
+                )?;
+            }
+        }
+    }
+
+    Ok((distance, similarity))
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::Solution;
+
+    use crate::{
+        external::{compare, compare_lists},
+        Day1,
+    };
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn compare_lists_works() {
+        assert_eq!(
+            compare_lists::<u32>(EXAMPLE.as_bytes(), 1000).unwrap(),
+            (11, 31)
+        );
+        assert_eq!(
+            compare_lists::<i64>(EXAMPLE.as_bytes(), 1000).unwrap(),
+            (11, 31)
+        );
+    }
+
+    #[test]
+    fn compare_lists_with_huge_chunk_size_works() {
+        assert_eq!(
+            compare_lists::<u32>(EXAMPLE.as_bytes(), 100_000_000_000_000).unwrap(),
+            (11, 31)
+        );
+        assert_eq!(
+            compare_lists::<u64>(EXAMPLE.as_bytes(), usize::MAX).unwrap(),
+            (11, 31)
+        );
+    }
+
+    #[test]
+    fn compare_lists_with_many_runs_works() {
+        assert_eq!(
+            compare_lists::<u32>(EXAMPLE.as_bytes(), 2).unwrap(),
+            (11, 31)
+        );
+        assert_eq!(
+            compare_lists::<u32>(EXAMPLE.as_bytes(), 1).unwrap(),
+            (11, 31)
+        );
+    }
+
+    #[test]
+    fn compare_lists_matches_in_memory() {
+        let input = (0..500_u32)
+            .map(|i| format!("{}   {}\n", (i * 7919) % 101, (i * 104_729) % 97))
+            .collect::<String>();
+        let lists = Day1::parse(&input).unwrap();
+
+        assert_eq!(
+            compare_lists(input.as_bytes(), 64).unwrap(),
+            (Day1::part1(&lists).unwrap(), Day1::part2(&lists).unwrap())
+        );
+    }
+
+    #[test]
+    fn compare_lists_with_bounded_fan_in_works() {
+        let input = (0..500_u32)
+            .map(|i| format!("{}   {}\n", (i * 7919) % 101, (i * 104_729) % 97))
+            .collect::<String>();
+
+        assert_eq!(
+            compare::<u32>(input.as_bytes(), 3, 2).unwrap(),
+            compare_lists::<u32>(input.as_bytes(), 1000).unwrap()
+        );
+        assert_eq!(compare::<u32>(EXAMPLE.as_bytes(), 1, 4).unwrap(), (11, 31));
+    }
+
+    #[test]
+    fn compare_lists_reports_bad_line() {
+        assert!(compare_lists::<u32>("3   4\n4\n".as_bytes(), 1).is_err());
+        assert!(compare_lists::<u32>("4000000000   1\n4000000000   2\n".as_bytes(), 1).is_err());
+    }
+}
@@ -0,0 +1,189 @@
+//! Random location lists in the puzzle format, for stress-testing and benchmarking.
+
+use std::{io::Write, ops::RangeInclusive};
+
+use aoc_common::rng::Rng;
+
+/// Number of already generated IDs duplicates are drawn from.
+const POOL_SIZE: usize = 1024;
+
+/// Settings of the generated lists, the same settings always generate the same lists.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Generator {
+    pub rows: usize,
+    /// IDs are drawn uniformly from the range unless duplicated. It spans both `i64` and `u64`,
+    /// so the lists can be parsed with any `--width`, as far as the IDs fit it.
+    pub range: RangeInclusive<i128>,
+    /// Probability of an ID repeating one generated before instead of being drawn anew.
+    pub duplicates: f64,
+    pub seed: u64,
+}
+
+impl Default for Generator {
+    /// Lists as large as the puzzle input, with five digit IDs.
+    fn default() -> Self {
+        Self {
+            rows: 1000,
+            range: 10_000..=99_999,
+            duplicates: 0.1,
+            seed: 2024,
+        }
+    }
+}
+
+/// Number within the inclusive `range`, uniformly distributed unless the range is wider than
+/// `u64`, where the bias of the modulo is accepted for test data.
+fn draw(rng: &mut Rng, range: &RangeInclusive<i128>) -> i128 {
+    let (start, end) = (*range.start(), *range.end());
+    let span = end.abs_diff(start);
+
+    let offset = match u64::try_from(span) {
+        Ok(span) => rng.range(0..=span) as u128,
+        Err(_) => {
+            let random = (rng.next_u64() as u128) << 64 | rng.next_u64() as u128;
+            span.checked_add(1).map_or(random, |bound| random % bound)
+        }
+    };
+
+    start.wrapping_add_unsigned(offset)
+}
+
+impl Generator {
+    /// Writes the lists to `out` line by line, so they do not need to fit in memory.
+    pub fn write(&self, mut out: impl Write) -> aoc_common::Result<()> {
+        let mut rng = Rng::new(self.seed);
+        // reservoir sample of the generated IDs to pick duplicates from
+        let mut pool = Vec::with_capacity(POOL_SIZE);
+        let mut generated = 0_u64;
+
+        let mut next = |rng: &mut Rng| {
+            if !pool.is_empty() && rng.chance(self.duplicates) {
+                return pool[rng.below(pool.len() as u64) as usize];
+            }
+
+            let id = draw(rng, &self.range);
+            generated += 1;
+            if pool.len() < POOL_SIZE {
+                pool.push(id);
+            } else if let Some(slot) = pool.get_mut(rng.below(generated) as usize) {
+                *slot = id;
+            }
+
+            id
+        };
+
+        for _ in 0..self.rows {
+            let (l1n, l2n) = (next(&mut rng), next(&mut rng));
+            writeln!(out, "{}   {}", l1n, l2n)?;
+        }
+
+        out.flush()?;
+
+        Ok(())
+    }
+
+    pub fn generate(&self) -> String {
+        let mut out = Vec::new();
+        self.write(&mut out)
+            .expect("writing to a Vec does not fail");
+
+        String::from_utf8(out).expect("generated lists are ASCII")
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::Solution;
+
+    use crate::{generate::Generator, group, location::LocationId, Day1Of};
+
+    #[test]
+    fn generate_is_reproducible() {
+        let generator = Generator {
+            rows: 100,
+            ..Generator::default()
+        };
+
+        assert_eq!(generator.generate(), generator.generate());
+        assert_ne!(
+            generator.generate(),
+            Generator {
+                seed: 1,
+                ..generator.clone()
+            }
+            .generate()
+        );
+    }
+
+    #[test]
+    fn generate_respects_settings() {
+        let repeated = |generator: &Generator| {
+            let lists = Day1Of::<u64>::parse(&generator.generate()).unwrap();
+
+            assert_eq!(lists.list1().len(), generator.rows);
+            assert!(lists
+                .list1()
+                .iter()
+                .all(|&id| generator.range.contains(&(id as i128))));
+
+            group(lists.list1())
+                .values()
+                .filter(|&&count| count > 1)
+                .count()
+        };
+
+        let generator = Generator {
+            rows: 500,
+            range: 1..=1_000_000_000_000,
+            duplicates: 0.5,
+            seed: 7,
+        };
+        assert!(repeated(&generator) > 50);
+        assert_eq!(
+            repeated(&Generator {
+                duplicates: 0.0,
+                ..generator
+            }),
+            0
+        );
+    }
+
+    #[test]
+    fn generate_covers_wide_ranges() {
+        fn ids<T: LocationId + Into<i128>>(range: std::ops::RangeInclusive<i128>) -> Vec<i128> {
+            let generator = Generator {
+                rows: 200,
+                range,
+                ..Generator::default()
+            };
+            let lists = Day1Of::<T>::parse(&generator.generate()).unwrap();
+
+            let ids: Vec<i128> = lists.list1().iter().map(|&id| id.into()).collect();
+            assert!(ids.iter().all(|id| generator.range.contains(id)));
+
+            ids
+        }
+
+        let negative = ids::<i64>(i64::MIN as i128..=-1);
+        assert!(negative.iter().any(|&id| id < i32::MIN as i128));
+
+        let full = ids::<i64>(i64::MIN as i128..=i64::MAX as i128);
+        assert!(full.iter().any(|&id| id < 0) && full.iter().any(|&id| id > 0));
+
+        let large = ids::<u64>(u64::MAX as i128 - 1000..=u64::MAX as i128);
+        assert!(large.iter().all(|&id| id > i64::MAX as i128));
+
+        let beyond_u64: Vec<u128> = Day1Of::<u128>::parse(
+            &Generator {
+                rows: 200,
+                range: 0..=i128::MAX,
+                ..Generator::default()
+            }
+            .generate(),
+        )
+        .unwrap()
+        .list1()
+        .to_vec();
+        assert!(beyond_u64.iter().any(|&id| id > u64::MAX as u128));
+    }
+}
@@ -0,0 +1,205 @@
+//! Day 1: Historian Hysteria.
+
+use std::{collections::HashMap, marker::PhantomData};
+
+use aoc_common::{parse, Solution};
+use location::{checked, LocationId};
+#[cfg(feature = "parallel")]
+use rayon::prelude::*;
+
+pub mod explain;
+pub mod external;
+pub mod generate;
+pub mod location;
+pub mod matrix;
+pub mod metric;
+
+/// Solution for location IDs of type `T`, see [`Day1`] for the default width.
+pub struct Day1Of<T>(PhantomData<T>);
+
+pub type Day1 = Day1Of<u32>;
+
+/// Two location lists, each sorted in ascending order.
+pub struct LocationLists<T = u32> {
+    list1: Vec<T>,
+    list2: Vec<T>,
+}
+
+impl<T: LocationId> LocationLists<T> {
+    pub fn new(mut list1: Vec<T>, mut list2: Vec<T>) -> Self {
+        #[cfg(not(feature = "parallel"))]
+        {
+            list1.sort();
+            list2.sort();
+        }
+        #[cfg(feature = "parallel")]
+        rayon::join(|| list1.par_sort_unstable(), || list2.par_sort_unstable());
+
+        Self { list1, list2 }
+    }
+
+    pub fn list1(&self) -> &[T] {
+        &self.list1
+    }
+
+    pub fn list2(&self) -> &[T] {
+        &self.list2
+    }
+}
+
+/// Counts occurrences of every location ID in the list.
+#[cfg(not(feature = "parallel"))]
+pub fn group<T: LocationId>(list: &[T]) -> HashMap<T, usize> {
+    let mut groupped = HashMap::new();
+    for &id in list {
+        groupped.entry(id).and_modify(|el| *el += 1).or_insert(1);
+    }
+
+    groupped
+}
+
+/// Counts occurrences of every location ID in the list, merging the counts of its chunks.
+#[cfg(feature = "parallel")]
+pub fn group<T: LocationId>(list: &[T]) -> HashMap<T, usize> {
+    list.par_iter()
+        .fold(HashMap::new, |mut groupped, &id| {
+            groupped.entry(id).and_modify(|el| *el += 1).or_insert(1);
+            groupped
+        })
+        .reduce(HashMap::new, |mut groupped, chunk| {
+            for (id, count) in chunk {
+                groupped
+                    .entry(id)
+                    .and_modify(|el| *el += count)
+                    .or_insert(count);
+            }
+            groupped
+        })
+}
+
+fn parse_line<T: LocationId>(idx: usize, line: &str) -> aoc_common::Result<[T; 2]> {
+    parse::exactly(parse::whitespace_tokens(line), line, idx + 1, "location ID")
+}
+
+/// Total distance between the lists, both sorted in ascending order.
+pub fn distance<T: LocationId>(list1: &[T], list2: &[T]) -> aoc_common::Result<T> {
+    let mut distance = T::default();
+    for (&l1n, &l2n) in list1.iter().zip(list2.iter()) {
+        let dist = checked(l1n.distance(l2n), "distance")?;
+        distance = checked(distance.checked_add(dist), "distance")?;
+    }
+
+    Ok(distance)
+}
+
+/// Similarity score of `list1` against the second list grouped with [`group`].
+pub fn similarity<T: LocationId>(
+    list1: &[T],
+    list2_groupped: &HashMap<T, usize>,
+) -> aoc_common::Result<T> {
+    let mut similarity = T::default();
+    for &l1n in list1 {
+        let sim = match list2_groupped.get(&l1n) {
+            Some(&quantity) => checked(
+                T::from_count(quantity).and_then(|q| l1n.checked_mul(q)),
+                "similarity",
+            )?,
+            None => T::default(),
+        };
+        similarity = checked(similarity.checked_add(sim), "similarity")?;
+    }
+
+    Ok(similarity)
+}
+
+impl<T: LocationId> Solution for Day1Of<T> {
+    const DAY: u8 = 1;
+    const PART1: &'static str = "Distance";
+    const PART2: &'static str = "Similarity";
+
+    type Input = LocationLists<T>;
+    type Answer1 = T;
+    type Answer2 = T;
+
+    #[cfg(not(feature = "parallel"))]
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        let mut list1 = Vec::new();
+        let mut list2 = Vec::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let [l1n, l2n] = parse_line(idx, line)?;
+
+            list1.push(l1n);
+            list2.push(l2n);
+        }
+
+        Ok(LocationLists::new(list1, list2))
+    }
+
+    /// Parses the lines on all cores, if several are malformed any of them may be reported.
+    #[cfg(feature = "parallel")]
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        let lines: Vec<&str> = input.lines().collect();
+        let pairs = lines
+            .par_iter()
+            .enumerate()
+            .map(|(idx, line)| parse_line(idx, line))
+            .collect::<aoc_common::Result<Vec<[T; 2]>>>()?;
+
+        let (list1, list2) = pairs.into_par_iter().map(|[l1n, l2n]| (l1n, l2n)).unzip();
+
+        Ok(LocationLists::new(list1, list2))
+    }
+
+    /// Total distance between the lists.
+    fn part1(input: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        distance(&input.list1, &input.list2)
+    }
+
+    /// Similarity score of the lists.
+    fn part2(input: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        similarity(&input.list1, &group(&input.list2))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{Error, Solution};
+
+    use crate::{Day1, Day1Of};
+
+    #[test]
+    fn part1_reports_overflow() {
+        let lists = Day1::parse("4000000000   1\n4000000000   2\n").unwrap();
+        assert!(matches!(
+            Day1::part1(&lists),
+            Err(Error::Overflow {
+                quantity: "distance",
+                width: "u32"
+            })
+        ));
+
+        let lists = Day1Of::<u64>::parse("4000000000   1\n4000000000   2\n").unwrap();
+        assert_eq!(Day1Of::<u64>::part1(&lists).unwrap(), 7_999_999_997);
+    }
+
+    #[test]
+    fn part2_reports_overflow() {
+        let lists = Day1::parse("3000000000   3000000000\n1   3000000000\n").unwrap();
+        assert!(matches!(
+            Day1::part2(&lists),
+            Err(Error::Overflow {
+                quantity: "similarity",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn signed_ids_work() {
+        let lists = Day1Of::<i64>::parse("-3   4\n4   -3\n").unwrap();
+
+        assert_eq!(Day1Of::<i64>::part1(&lists).unwrap(), 0);
+        assert_eq!(Day1Of::<i64>::part2(&lists).unwrap(), 1);
+    }
+}
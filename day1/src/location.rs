@@ -0,0 +1,75 @@
+//! Integer types usable as location IDs.
+
+use std::{any, fmt::Display, hash::Hash, str::FromStr};
+
+use aoc_common::Error;
+
+/// Location ID of a fixed integer width, with the arithmetic needed to compare the lists
+/// checked for overflow.
+pub trait LocationId:
+    Copy + Ord + Hash + Default + Display + FromStr<Err: Display> + Send + Sync + 'static
+{
+    /// Absolute difference between the IDs, `None` if it does not fit the type.
+    fn distance(self, other: Self) -> Option<Self>;
+
+    fn checked_add(self, other: Self) -> Option<Self>;
+
+    fn checked_mul(self, other: Self) -> Option<Self>;
+
+    /// Converts the number of occurrences of an ID, `None` if it does not fit the type.
+    fn from_count(count: usize) -> Option<Self>;
+}
+
+macro_rules! impl_location_id {
+    ($($t:ty),*) => {
+        $(
+            impl LocationId for $t {
+                fn distance(self, other: Self) -> Option<Self> {
+                    // differences of signed types may not fit them, e.g. i64::MAX - i64::MIN
+                    Self::try_from(self.abs_diff(other)).ok()
+                }
+
+                fn checked_add(self, other: Self) -> Option<Self> {
+                    <$t>::checked_add(self, other)
+                }
+
+                fn checked_mul(self, other: Self) -> Option<Self> {
+                    <$t>::checked_mul(self, other)
+                }
+
+                fn from_count(count: usize) -> Option<Self> {
+                    Self::try_from(count).ok()
+                }
+            }
+        )*
+    };
+}
+
+impl_location_id!(u32, u64, i64, u128);
+
+/// Unwraps the result of a checked operation, reporting which `quantity` overflowed `T`.
+pub fn checked<T: LocationId>(value: Option<T>, quantity: &'static str) -> aoc_common::Result<T> {
+    value.ok_or(Error::Overflow {
+        quantity,
+        width: any::type_name::<T>(),
+    })
+}
+
+#[cfg(test)]
+mod test {
+    use crate::location::LocationId;
+
+    #[test]
+    fn distance_works() {
+        assert_eq!(LocationId::distance(3_u32, 7), Some(4));
+        assert_eq!(LocationId::distance(-3_i64, 7), Some(10));
+        assert_eq!(LocationId::distance(i64::MIN, i64::MAX), None);
+        assert_eq!(LocationId::distance(0_u128, u128::MAX), Some(u128::MAX));
+    }
+
+    #[test]
+    fn from_count_works() {
+        assert_eq!(<u32 as LocationId>::from_count(7), Some(7));
+        assert_eq!(<u32 as LocationId>::from_count(usize::MAX), None);
+    }
+}
@@ -1,44 +1,214 @@
 use std::{
-    collections::HashMap, fs::File, io::{self, BufRead, BufReader}
+    env,
+    io::{self, BufWriter, ErrorKind},
+    process::ExitCode,
+    time::Instant,
 };
 
-fn main() -> io::Result<()> {
-    let input_file = File::open("input.txt")?;
-    let reader = BufReader::new(input_file);
+use aoc_common::{
+    cli::{self, Args, Format, Options},
+    report::Report,
+    Error, Solution,
+};
+use day1::{
+    explain::{self, Explanation},
+    external,
+    generate::Generator,
+    location::LocationId,
+    matrix::{Columns, Matrix},
+    metric::Metric,
+    Day1, Day1Of,
+};
+
+const OPTIONS: Options = Options {
+    flags: &["--csv", "--explain", "--external", "--matrix"],
+    values: &["--chunk-size", "--metric", "--top", "--width"],
+    usage: "[--width u32|u64|i64|u128] \
+            [--external [--chunk-size <lines>] | --matrix | --metric sum|squared|max|emd|jaccard \
+            | --explain [--top <k>] [--csv]]",
+};
+
+const GENERATE_OPTIONS: Options = Options {
+    flags: &[],
+    values: &["--duplicates", "--max", "--min", "--rows", "--seed"],
+    usage: "[--rows <n>] [--min <id>] [--max <id>] [--duplicates <rate>] [--seed <n>]",
+};
+
+fn generator(args: &Args) -> Result<Generator, String> {
+    if let Some(arg) = &args.input {
+        return Err(format!("Unexpected argument: {}", arg));
+    }
+
+    let defaults = Generator::default();
+    let (min, max) = defaults.range.into_inner();
+    let generator = Generator {
+        rows: args.value("--rows")?.unwrap_or(defaults.rows),
+        range: args.value("--min")?.unwrap_or(min)..=args.value("--max")?.unwrap_or(max),
+        duplicates: args.value("--duplicates")?.unwrap_or(defaults.duplicates),
+        seed: args.value("--seed")?.unwrap_or(defaults.seed),
+    };
+
+    if generator.range.is_empty() {
+        return Err("--min must not exceed --max.".to_string());
+    }
+    if !(0.0..=1.0).contains(&generator.duplicates) {
+        return Err("Duplicate rate must be between 0 and 1.".to_string());
+    }
 
-    let mut list1 = Vec::new();
-    let mut list2 = Vec::new();
-    let mut list2_groupped = HashMap::new();
+    Ok(generator)
+}
+
+fn generate(args: impl IntoIterator<Item = String>) -> ExitCode {
+    let generator = match Args::parse(args, &GENERATE_OPTIONS).and_then(|args| generator(&args)) {
+        Ok(generator) => generator,
+        Err(e) => {
+            eprintln!("{}", e);
+            eprintln!("Usage: day1 generate {}", GENERATE_OPTIONS.usage);
+            return ExitCode::FAILURE;
+        }
+    };
+
+    match generator.write(BufWriter::new(io::stdout().lock())) {
+        Ok(()) => ExitCode::SUCCESS,
+        // the reader is done, e.g. `head`, which is not a failure of the generator
+        Err(Error::Io(e)) if e.kind() == ErrorKind::BrokenPipe => ExitCode::SUCCESS,
+        Err(e) => {
+            eprint!("{}", e.render(&"<stdout>", None));
+            ExitCode::FAILURE
+        }
+    }
+}
+
+fn matrix<T: LocationId>(args: &Args) -> ExitCode {
+    cli::with_input(args, |input| {
+        let matrix = Matrix::new(&Columns::<T>::parse(input)?)?;
 
-    for line in reader.lines() {
-        let list_items = line?
-            .split_ascii_whitespace()
-            .map(|el| el.parse::<u32>().unwrap())
-            .collect::<Vec<u32>>();
+        match args.format {
+            Format::Text => print!("{}", matrix.to_table()),
+            Format::Json => println!("{}", matrix.to_json()),
+        }
+
+        Ok(())
+    })
+}
+
+fn metric<T: LocationId>(args: &Args, metric: Metric) -> ExitCode {
+    cli::with_input(args, |input| {
+        let measure = metric.measure(&Day1Of::<T>::parse(input)?)?;
+
+        match args.format {
+            Format::Text => println!("{}: {}", metric.label(), measure),
+            Format::Json => println!(
+                "{{\"day\":1,\"metric\":\"{}\",\"value\":{}}}",
+                metric, measure
+            ),
+        }
+
+        Ok(())
+    })
+}
 
-        list1.push(list_items[0]);
-        list2.push(list_items[1]);
-        list2_groupped.entry(list_items[1]).and_modify(|el| *el += 1).or_insert(1);
+fn explain<T: LocationId>(args: &Args) -> ExitCode {
+    if args.format == Format::Json {
+        return cli::usage_error::<Day1>("--explain prints a table or CSV, not JSON.", &OPTIONS);
     }
 
-    list1.sort();
-    list2.sort();
+    let top = match args.value::<usize>("--top") {
+        Ok(top) => top.unwrap_or(explain::DEFAULT_TOP),
+        Err(e) => return cli::usage_error::<Day1>(&e, &OPTIONS),
+    };
 
-    let mut distance = 0;
-    let mut similarity = 0;
-    for (&l1n, &l2n) in list1.iter().zip(list2.iter()) {
-        // println!("{}:{}", l1n, l2n);
-        let dist = u32::abs_diff(l1n, l2n);
-        let sim = match list2_groupped.get(&l1n) {
-            Some(&quantity) => l1n * quantity,
-            None => 0
+    cli::with_input(args, |input| {
+        let explanation = Explanation::new(&Day1Of::<T>::parse(input)?)?;
+
+        if args.flag("--csv") {
+            print!("{}", explanation.to_csv(top));
+        } else {
+            print!("{}", explanation.to_table(top));
+        }
+
+        Ok(())
+    })
+}
+
+fn run<T: LocationId>(args: &Args) -> ExitCode {
+    let modes = ["--external", "--matrix", "--metric", "--explain"];
+    if modes.iter().filter(|&&mode| args.flag(mode)).count() > 1 {
+        return cli::usage_error::<Day1>(
+            "Only one of --external, --matrix, --metric and --explain can be given.",
+            &OPTIONS,
+        );
+    }
+    if args.flag("--top") && !args.flag("--explain") {
+        return cli::usage_error::<Day1>("--top requires --explain.", &OPTIONS);
+    }
+    if args.flag("--csv") && !args.flag("--explain") {
+        return cli::usage_error::<Day1>("--csv requires --explain.", &OPTIONS);
+    }
+    if args.flag("--chunk-size") && !args.flag("--external") {
+        return cli::usage_error::<Day1>("--chunk-size requires --external.", &OPTIONS);
+    }
+
+    if args.flag("--explain") {
+        return explain::<T>(args);
+    }
+
+    if args.flag("--matrix") {
+        return matrix::<T>(args);
+    }
+
+    match args.value::<Metric>("--metric") {
+        Ok(Some(m)) => return metric::<T>(args, m),
+        Ok(None) => (),
+        Err(e) => return cli::usage_error::<Day1>(&e, &OPTIONS),
+    }
+
+    if !args.flag("--external") {
+        return cli::solve::<Day1Of<T>>(args);
+    }
+
+    let chunk_size = match args.value::<usize>("--chunk-size") {
+        Ok(Some(0)) => return cli::usage_error::<Day1>("Chunk size must be positive.", &OPTIONS),
+        Ok(chunk_size) => chunk_size.unwrap_or(external::DEFAULT_CHUNK_SIZE),
+        Err(e) => return cli::usage_error::<Day1>(&e, &OPTIONS),
+    };
+
+    cli::with_source(args, |source| {
+        let started = Instant::now();
+        let (distance, similarity) = external::compare_lists::<T>(source.open()?, chunk_size)?;
+
+        let report = Report {
+            day: 1,
+            part1: Some(distance.to_string()),
+            part2: Some(similarity.to_string()),
+            elapsed: started.elapsed(),
         };
-        distance += dist;
-        similarity += sim;
+        cli::print::<Day1>(&report, args.format);
+
+        Ok(())
+    })
+}
+
+fn main() -> ExitCode {
+    if env::args().nth(1).as_deref() == Some("generate") {
+        return generate(env::args().skip(2));
     }
 
-    println!("Distance: {}", distance);
-    println!("Similarity: {}", similarity);
+    let args = match cli::args::<Day1>(&OPTIONS) {
+        Ok(args) => args,
+        Err(code) => return code,
+    };
 
-    Ok(())
+    match args
+        .value::<String>("--width")
+        .as_ref()
+        .map(Option::as_deref)
+    {
+        Ok(None | Some("u32")) => run::<u32>(&args),
+        Ok(Some("u64")) => run::<u64>(&args),
+        Ok(Some("i64")) => run::<i64>(&args),
+        Ok(Some("u128")) => run::<u128>(&args),
+        Ok(Some(width)) => cli::usage_error::<Day1>(&format!("Invalid width: {}", width), &OPTIONS),
+        Err(e) => cli::usage_error::<Day1>(e, &OPTIONS),
+    }
 }
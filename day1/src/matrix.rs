@@ -0,0 +1,195 @@
+//! Comparison of any number of location lists given as whitespace-separated columns.
+
+use aoc_common::{parse, Error};
+
+use crate::{distance, group, location::LocationId, similarity};
+
+/// Location lists read column by column, each sorted in ascending order.
+pub struct Columns<T = u32> {
+    columns: Vec<Vec<T>>,
+}
+
+impl<T: LocationId> Columns<T> {
+    /// Parses one list per column, every line is expected to have as many location IDs as the
+    /// first one.
+    pub fn parse(input: &str) -> aoc_common::Result<Self> {
+        let mut columns: Vec<Vec<T>> = Vec::new();
+
+        for (idx, line) in input.lines().enumerate() {
+            let ids = parse::whitespace_separated::<T>(line, idx + 1)?;
+
+            if idx == 0 {
+                columns = vec![Vec::new(); ids.len()];
+            }
+
+            if ids.len() < columns.len() || ids.is_empty() {
+                return Err(Error::MissingToken {
+                    line: idx + 1,
+                    column: parse::end_column(line),
+                    expected: "location ID",
+                });
+            }
+            if let Some(extra) = parse::whitespace_tokens(line).nth(columns.len()) {
+                return Err(Error::UnexpectedToken {
+                    line: idx + 1,
+                    column: extra.column,
+                    token: extra.text.to_string(),
+                    expected: "end of line",
+                });
+            }
+
+            for (column, id) in columns.iter_mut().zip(ids) {
+                column.push(id);
+            }
+        }
+
+        for column in columns.iter_mut() {
+            column.sort();
+        }
+
+        Ok(Self { columns })
+    }
+
+    pub fn columns(&self) -> &[Vec<T>] {
+        &self.columns
+    }
+}
+
+/// Distance and similarity score of every pair of columns.
+///
+/// `distance[i][j]` is symmetric, while `similarity[i][j]` scores column `i` against column `j`.
+#[derive(Debug, PartialEq)]
+pub struct Matrix<T = u32> {
+    pub distance: Vec<Vec<T>>,
+    pub similarity: Vec<Vec<T>>,
+}
+
+impl<T: LocationId> Matrix<T> {
+    pub fn new(columns: &Columns<T>) -> aoc_common::Result<Self> {
+        let columns = columns.columns();
+        let groupped: Vec<_> = columns.iter().map(|c| group(c)).collect();
+
+        let mut distances = Vec::with_capacity(columns.len());
+        let mut similarities = Vec::with_capacity(columns.len());
+        for list1 in columns {
+            distances.push(
+                columns
+                    .iter()
+                    .map(|list2| distance(list1, list2))
+                    .collect::<aoc_common::Result<Vec<_>>>()?,
+            );
+            similarities.push(
+                groupped
+                    .iter()
+                    .map(|list2_groupped| similarity(list1, list2_groupped))
+                    .collect::<aoc_common::Result<Vec<_>>>()?,
+            );
+        }
+
+        Ok(Self {
+            distance: distances,
+            similarity: similarities,
+        })
+    }
+
+    /// Formats both matrices as tables with 1-based column numbers as headers.
+    pub fn to_table(&self) -> String {
+        let mut table = String::new();
+        for (title, matrix) in [
+            ("Distance", &self.distance),
+            ("Similarity", &self.similarity),
+        ] {
+            table += &format!("{:<12}", title);
+            for j in 1..=matrix.len() {
+                table += &format!("{:>12}", j);
+            }
+            table += "\n";
+
+            for (i, row) in matrix.iter().enumerate() {
+                table += &format!("{:<12}", i + 1);
+                for value in row {
+                    table += &format!("{:>12}", value);
+                }
+                table += "\n";
+            }
+        }
+
+        table
+    }
+
+    /// Formats the matrices as a single-line JSON object of nested arrays.
+    pub fn to_json(&self) -> String {
+        let rows = |matrix: &[Vec<T>]| {
+            matrix
+                .iter()
+                .map(|row| {
+                    let values: Vec<_> = row.iter().map(T::to_string).collect();
+                    format!("[{}]", values.join(","))
+                })
+                .collect::<Vec<_>>()
+                .join(",")
+        };
+
+        format!(
+            "{{\"day\":1,\"columns\":{},\"distance\":[{}],\"similarity\":[{}]}}",
+            self.distance.len(),
+            rows(&self.distance),
+            rows(&self.similarity)
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{Error, Solution};
+
+    use crate::{
+        matrix::{Columns, Matrix},
+        Day1,
+    };
+
+    const EXAMPLE: &str = "3   4   1\n4   3   3\n2   5   3\n1   3   9\n3   9   4\n3   3   3\n";
+
+    #[test]
+    fn matrix_works() {
+        let matrix = Matrix::new(&Columns::<u32>::parse(EXAMPLE).unwrap()).unwrap();
+
+        let lists = Day1::parse("3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n").unwrap();
+        assert_eq!(matrix.distance[0][1], Day1::part1(&lists).unwrap());
+        assert_eq!(matrix.similarity[0][1], Day1::part2(&lists).unwrap());
+
+        assert_eq!(matrix.distance[0], vec![0, 11, 7]);
+        assert_eq!(matrix.distance[2][0], 7);
+        assert_eq!(matrix.similarity[2], vec![32, 40, 41]);
+    }
+
+    #[test]
+    fn parse_rejects_ragged_rows() {
+        assert!(matches!(
+            Columns::<u32>::parse("1 2 3\n4 5\n"),
+            Err(Error::MissingToken {
+                line: 2,
+                column: 4,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Columns::<u32>::parse("1 2\n4 5 6\n"),
+            Err(Error::UnexpectedToken {
+                line: 2,
+                column: 5,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn to_json_works() {
+        let matrix = Matrix::new(&Columns::<u32>::parse("1 2\n3 3\n").unwrap()).unwrap();
+
+        assert_eq!(
+            matrix.to_json(),
+            r#"{"day":1,"columns":2,"distance":[[0,1],[1,0]],"similarity":[[4,3],[3,5]]}"#
+        );
+    }
+}
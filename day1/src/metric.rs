@@ -0,0 +1,237 @@
+//! Alternative ways of comparing the location lists.
+
+use std::{collections::HashMap, fmt, str::FromStr};
+
+use crate::{
+    distance, group,
+    location::{checked, LocationId},
+    LocationLists,
+};
+
+/// Metric comparing two location lists.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Metric {
+    /// Sum of distances between the sorted pairs, the answer to part 1.
+    #[default]
+    Sum,
+    /// Sum of squared distances between the sorted pairs.
+    Squared,
+    /// Largest distance between the sorted pairs.
+    Max,
+    /// Earth mover's distance between the histograms of the lists.
+    EarthMovers,
+    /// Jaccard similarity of the lists as multisets.
+    Jaccard,
+}
+
+impl Metric {
+    /// Name of the measured quantity, used to label text output.
+    pub fn label(&self) -> &'static str {
+        match self {
+            Metric::Sum => "Distance",
+            Metric::Squared => "Squared distance",
+            Metric::Max => "Max distance",
+            Metric::EarthMovers => "Earth mover's distance",
+            Metric::Jaccard => "Jaccard similarity",
+        }
+    }
+
+    pub fn measure<T: LocationId>(
+        &self,
+        lists: &LocationLists<T>,
+    ) -> aoc_common::Result<Measure<T>> {
+        let (list1, list2) = (lists.list1(), lists.list2());
+
+        match self {
+            Metric::Sum => distance(list1, list2).map(Measure::Total),
+            Metric::Squared => squared(list1, list2).map(Measure::Total),
+            Metric::Max => max(list1, list2).map(Measure::Total),
+            Metric::EarthMovers => earth_movers(&group(list1), &group(list2)).map(Measure::Total),
+            Metric::Jaccard => Ok(Measure::Ratio(jaccard(&group(list1), &group(list2)))),
+        }
+    }
+}
+
+impl FromStr for Metric {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "sum" => Ok(Metric::Sum),
+            "squared" => Ok(Metric::Squared),
+            "max" => Ok(Metric::Max),
+            "emd" => Ok(Metric::EarthMovers),
+            "jaccard" => Ok(Metric::Jaccard),
+            _ => Err(format!("Invalid metric: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Metric {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Metric::Sum => write!(f, "sum"),
+            Metric::Squared => write!(f, "squared"),
+            Metric::Max => write!(f, "max"),
+            Metric::EarthMovers => write!(f, "emd"),
+            Metric::Jaccard => write!(f, "jaccard"),
+        }
+    }
+}
+
+/// Value of a metric, either a total in the location ID type or a ratio.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Measure<T> {
+    Total(T),
+    Ratio(f64),
+}
+
+impl<T: fmt::Display> fmt::Display for Measure<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Measure::Total(total) => write!(f, "{}", total),
+            Measure::Ratio(ratio) => write!(f, "{:.6}", ratio),
+        }
+    }
+}
+
+/// Sum of squared distances between the lists, both sorted in ascending order.
+pub fn squared<T: LocationId>(list1: &[T], list2: &[T]) -> aoc_common::Result<T> {
+    let mut squared = T::default();
+    for (&l1n, &l2n) in list1.iter().zip(list2.iter()) {
+        let dist = checked(l1n.distance(l2n), "squared distance")?;
+        let dist = checked(dist.checked_mul(dist), "squared distance")?;
+        squared = checked(squared.checked_add(dist), "squared distance")?;
+    }
+
+    Ok(squared)
+}
+
+/// Largest distance between the lists, both sorted in ascending order.
+pub fn max<T: LocationId>(list1: &[T], list2: &[T]) -> aoc_common::Result<T> {
+    let mut max = T::default();
+    for (&l1n, &l2n) in list1.iter().zip(list2.iter()) {
+        max = max.max(checked(l1n.distance(l2n), "max distance")?);
+    }
+
+    Ok(max)
+}
+
+/// Earth mover's distance between the lists grouped with [`group`]: the total number of
+/// location ID units the entries of the first list have to be moved by to turn it into the
+/// second one.
+///
+/// Computed by accumulating the difference of cumulative counts across the gaps between the
+/// distinct IDs; for lists of the same length it equals their total [`distance`].
+pub fn earth_movers<T: LocationId>(
+    list1_groupped: &HashMap<T, usize>,
+    list2_groupped: &HashMap<T, usize>,
+) -> aoc_common::Result<T> {
+    let mut ids: Vec<T> = list1_groupped
+        .keys()
+        .chain(list2_groupped.keys())
+        .copied()
+        .collect();
+    ids.sort();
+    ids.dedup();
+
+    let (mut cumulative1, mut cumulative2) = (0_usize, 0_usize);
+    let mut work = T::default();
+    for pair in ids.windows(2) {
+        cumulative1 += list1_groupped.get(&pair[0]).copied().unwrap_or_default();
+        cumulative2 += list2_groupped.get(&pair[0]).copied().unwrap_or_default();
+
+        let surplus = checked(
+            T::from_count(cumulative1.abs_diff(cumulative2)),
+            "earth mover's distance",
+        )?;
+        let gap = checked(pair[1].distance(pair[0]), "earth mover's distance")?;
+        let moved = checked(surplus.checked_mul(gap), "earth mover's distance")?;
+        work = checked(work.checked_add(moved), "earth mover's distance")?;
+    }
+
+    Ok(work)
+}
+
+/// Jaccard similarity of the lists grouped with [`group`] as multisets, `1` for two empty lists.
+pub fn jaccard<T: LocationId>(
+    list1_groupped: &HashMap<T, usize>,
+    list2_groupped: &HashMap<T, usize>,
+) -> f64 {
+    let (mut intersection, mut union) = (0_usize, 0_usize);
+    for (id, &count1) in list1_groupped {
+        let count2 = list2_groupped.get(id).copied().unwrap_or_default();
+        intersection += count1.min(count2);
+        union += count1.max(count2);
+    }
+    for (id, &count2) in list2_groupped {
+        if !list1_groupped.contains_key(id) {
+            union += count2;
+        }
+    }
+
+    if union == 0 {
+        return 1.0;
+    }
+
+    intersection as f64 / union as f64
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{Error, Solution};
+
+    use crate::{
+        metric::{Measure, Metric},
+        Day1, Day1Of,
+    };
+
+    const EXAMPLE: &str = "3   4\n4   3\n2   5\n1   3\n3   9\n3   3\n";
+
+    #[test]
+    fn measure_works() {
+        let lists = Day1::parse(EXAMPLE).unwrap();
+
+        assert_eq!(Metric::Sum.measure(&lists).unwrap(), Measure::Total(11));
+        assert_eq!(Metric::Squared.measure(&lists).unwrap(), Measure::Total(35));
+        assert_eq!(Metric::Max.measure(&lists).unwrap(), Measure::Total(5));
+        assert_eq!(
+            Metric::EarthMovers.measure(&lists).unwrap(),
+            Measure::Total(11)
+        );
+        assert_eq!(
+            Metric::Jaccard.measure(&lists).unwrap(),
+            Measure::Ratio(0.5)
+        );
+    }
+
+    #[test]
+    fn earth_movers_works_for_signed_ids() {
+        let lists = Day1Of::<i64>::parse("-5   2\n-5   2\n7   2\n").unwrap();
+
+        assert_eq!(
+            Metric::EarthMovers.measure(&lists).unwrap(),
+            Measure::Total(19)
+        );
+    }
+
+    #[test]
+    fn squared_reports_overflow() {
+        let lists = Day1::parse("70000   0\n").unwrap();
+
+        assert!(matches!(
+            Metric::Squared.measure(&lists),
+            Err(Error::Overflow {
+                quantity: "squared distance",
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn parse_works() {
+        assert_eq!("emd".parse(), Ok(Metric::EarthMovers));
+        assert_eq!(Metric::Jaccard.to_string().parse(), Ok(Metric::Jaccard));
+        assert!("median".parse::<Metric>().is_err());
+    }
+}
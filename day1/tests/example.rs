@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day1::Day1;
+
+const EXAMPLE: &str = include_str!("fixtures/example.txt");
+
+#[test]
+fn part1_example() {
+    let lists = Day1::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day1::part1(&lists).unwrap(), 11);
+}
+
+#[test]
+fn part2_example() {
+    let lists = Day1::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day1::part2(&lists).unwrap(), 31);
+}
@@ -0,0 +1,118 @@
+//! Problem Dampener generalized to removing any number of levels.
+
+use std::cmp::Ordering;
+
+use crate::policy::SafetyPolicy;
+
+/// Indices of the levels in the longest subsequence of the report going in the given `ordering`
+/// and following the `policy`.
+///
+/// Like the longest increasing subsequence: `longest[i]` is the length of the longest valid
+/// subsequence ending with level `i`, extending the best of the earlier levels it can follow.
+fn longest_valid(report: &[u32], policy: &SafetyPolicy, ordering: Ordering) -> Vec<usize> {
+    let mut longest = vec![1; report.len()];
+    let mut previous = vec![None; report.len()];
+
+    for i in 1..report.len() {
+        for j in 0..i {
+            if policy.violation(ordering, report[j], report[i]).is_none()
+                && longest[j] + 1 > longest[i]
+            {
+                longest[i] = longest[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let Some(mut last) = (0..report.len()).max_by_key(|&i| longest[i]) else {
+        return Vec::new();
+    };
+
+    let mut kept = vec![last];
+    while let Some(prev) = previous[last] {
+        kept.push(prev);
+        last = prev;
+    }
+    kept.reverse();
+
+    kept
+}
+
+/// Indices of the levels in the longest subsequence of the report following the `policy`.
+pub fn longest_safe_subsequence(report: &[u32], policy: &SafetyPolicy) -> Vec<usize> {
+    policy
+        .orderings()
+        .iter()
+        .map(|&ordering| longest_valid(report, policy, ordering))
+        .max_by_key(Vec::len)
+        .unwrap_or_default()
+}
+
+/// Fewest levels that have to be removed from the report for it to follow the `policy`.
+pub fn min_removals(report: &[u32], policy: &SafetyPolicy) -> usize {
+    report.len() - longest_safe_subsequence(report, policy).len()
+}
+
+/// Checks that the report follows the `policy` after removing at most `removals` levels.
+pub fn is_safe_report_with_removals(
+    report: &[u32],
+    policy: &SafetyPolicy,
+    removals: usize,
+) -> bool {
+    min_removals(report, policy) <= removals
+}
+
+/// Number of reports safe after removing at most `K` levels, for every `K` from 0 up to the
+/// length of the longest report.
+pub fn count_safe_by_removals(reports: &[Vec<u32>], policy: &SafetyPolicy) -> Vec<usize> {
+    let longest_report = reports.iter().map(Vec::len).max().unwrap_or_default();
+
+    let mut counts = vec![0; longest_report + 1];
+    for report in reports {
+        counts[min_removals(report, policy)] += 1;
+    }
+
+    // turn the number of reports needing exactly K removals into those needing at most K
+    for k in 1..counts.len() {
+        counts[k] += counts[k - 1];
+    }
+
+    counts
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::Solution;
+
+    use crate::{
+        dampener::{count_safe_by_removals, is_safe_report_with_removals, min_removals},
+        policy::SafetyPolicy,
+        Day2,
+    };
+
+    #[test]
+    fn min_removals_works() {
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(min_removals(&[7, 6, 4, 2, 1], &policy), 0);
+        assert_eq!(min_removals(&[1, 3, 2, 4, 5], &policy), 1);
+        assert_eq!(min_removals(&[9, 7, 6, 2, 1], &policy), 2);
+        assert_eq!(min_removals(&[5, 8, 4, 3, 2, 1], &policy), 1);
+        assert_eq!(min_removals(&[1, 9, 2, 8, 3, 7, 4], &policy), 3);
+        assert_eq!(min_removals(&[4], &policy), 0);
+
+        assert!(is_safe_report_with_removals(&[1, 2, 7, 8, 9], &policy, 2));
+        assert!(!is_safe_report_with_removals(&[1, 2, 7, 8, 9], &policy, 1));
+    }
+
+    #[test]
+    fn count_safe_by_removals_works() {
+        let reports =
+            Day2::parse("7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n")
+                .unwrap();
+        let counts = count_safe_by_removals(&reports, &SafetyPolicy::default());
+
+        assert_eq!(counts, vec![2, 4, 6, 6, 6, 6]);
+        assert_eq!(counts[1], Day2::part2(&reports).unwrap());
+    }
+}
@@ -0,0 +1,440 @@
+//! Day 2: Red-Nosed Reports.
+
+use std::{cmp::Ordering, fmt};
+
+use aoc_common::{parse, Error, Solution};
+use policy::{Rule, SafetyPolicy};
+
+pub mod dampener;
+pub mod policy;
+pub mod repair;
+pub mod stats;
+pub mod stream;
+
+/// Number of steps between adjacent levels going up, staying level and going down.
+fn step_counts(report: &[u32]) -> [(Ordering, usize); 3] {
+    let mut counts = [
+        (Ordering::Less, 0),
+        (Ordering::Equal, 0),
+        (Ordering::Greater, 0),
+    ];
+    for el in report.windows(2) {
+        match el[0].cmp(&el[1]) {
+            Ordering::Less => counts[0].1 += 1,
+            Ordering::Equal => counts[1].1 += 1,
+            Ordering::Greater => counts[2].1 += 1,
+        }
+    }
+
+    counts
+}
+
+/// Determines the prevailing direction of the levels in the report: `Less` if the levels are
+/// mostly increasing, `Greater` if mostly decreasing and `Equal` if mostly the same. Ties go to
+/// the first of `Less`, `Equal` and `Greater`.
+pub fn determine_ordering(report: &[u32]) -> Ordering {
+    step_counts(report)
+        .into_iter()
+        .rev()
+        .max_by_key(|&(_, count)| count)
+        .map(|(order, _)| order)
+        .unwrap()
+}
+
+/// Direction most of the steps of the report go in, `None` if the report has no steps or
+/// several directions are equally common.
+pub fn prevailing_ordering(report: &[u32]) -> Option<Ordering> {
+    let counts = step_counts(report);
+    let most = counts
+        .iter()
+        .map(|&(_, count)| count)
+        .max()
+        .unwrap_or_default();
+
+    let mut prevailing = counts.iter().filter(|&&(_, count)| count == most);
+    match (prevailing.next(), prevailing.next()) {
+        (Some(&(order, _)), None) if most > 0 => Some(order),
+        _ => None,
+    }
+}
+
+/// Level of a report breaking a rule.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Violation {
+    /// 0-based index of the level.
+    pub index: usize,
+    pub rule: Rule,
+}
+
+impl fmt::Display for Violation {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "level {} {}", self.index + 1, self.rule)
+    }
+}
+
+/// Outcome of checking a report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Verdict {
+    Safe,
+    /// Safe after the Problem Dampener removed the level at the 0-based index `removed`.
+    Dampened {
+        removed: usize,
+        violation: Violation,
+    },
+    Unsafe(Violation),
+}
+
+impl Verdict {
+    pub fn is_safe(&self) -> bool {
+        !matches!(self, Verdict::Unsafe(_))
+    }
+
+    /// Formats the verdict as a single-line JSON object with 1-based levels, like the text.
+    pub fn to_json(&self) -> String {
+        match self {
+            Verdict::Safe => r#"{"verdict":"safe"}"#.to_string(),
+            Verdict::Dampened { removed, violation } => format!(
+                "{{\"verdict\":\"dampened\",\"removed\":{},\"level\":{},\"rule\":\"{}\"}}",
+                removed + 1,
+                violation.index + 1,
+                violation.rule.code()
+            ),
+            Verdict::Unsafe(violation) => format!(
+                "{{\"verdict\":\"unsafe\",\"level\":{},\"rule\":\"{}\"}}",
+                violation.index + 1,
+                violation.rule.code()
+            ),
+        }
+    }
+}
+
+impl fmt::Display for Verdict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Verdict::Safe => write!(f, "safe"),
+            Verdict::Dampened { removed, violation } => write!(
+                f,
+                "safe after removing level {}, {}",
+                removed + 1,
+                violation
+            ),
+            Verdict::Unsafe(violation) => write!(f, "unsafe, {}", violation),
+        }
+    }
+}
+
+/// Checks that the report levels follow the `policy`, by default that they are strictly
+/// monotonic and adjacent levels differ by 1 to 3. Reports the first level breaking it.
+///
+/// When the report may go either way, it is blamed for the direction it follows the longest,
+/// increasing on a tie, so the same report always gets the same verdict.
+pub fn check_report(report: &[u32], policy: &SafetyPolicy) -> Verdict {
+    let mut latest: Option<Violation> = None;
+
+    for &ordering in policy.orderings() {
+        match first_violation(report, policy, ordering, None) {
+            None => return Verdict::Safe,
+            Some(violation) if latest.is_none_or(|latest| violation.index > latest.index) => {
+                latest = Some(violation)
+            }
+            Some(_) => {}
+        }
+    }
+
+    Verdict::Unsafe(latest.expect("policy allows at least one direction"))
+}
+
+/// First level breaking the `policy` in a report going in the given `ordering`, skipping the
+/// level at index `removed` if any.
+fn first_violation(
+    report: &[u32],
+    policy: &SafetyPolicy,
+    ordering: Ordering,
+    removed: Option<usize>,
+) -> Option<Violation> {
+    let mut levels = report
+        .iter()
+        .enumerate()
+        .filter(|&(idx, _)| Some(idx) != removed);
+    let (_, mut last_level) = levels.next()?;
+
+    for (idx, curr_level) in levels {
+        if let Some(rule) = policy.violation(ordering, *last_level, *curr_level) {
+            return Some(Violation { index: idx, rule });
+        }
+
+        last_level = curr_level;
+    }
+
+    None
+}
+
+/// Checks that the report follows the `policy`, possibly after the Problem Dampener removes
+/// a single level.
+///
+/// Whatever the removed level, the report has to go in one of the directions the policy allows.
+/// For each of them, the step into the first level breaking the policy has to be fixed, and the
+/// only removals changing that step are of the levels on either side of it.
+pub fn check_report_dampened(report: &[u32], policy: &SafetyPolicy) -> Verdict {
+    let violation = match check_report(report, policy) {
+        Verdict::Unsafe(violation) => violation,
+        verdict => return verdict,
+    };
+
+    for &ordering in policy.orderings() {
+        let Some(Violation { index: idx, .. }) = first_violation(report, policy, ordering, None)
+        else {
+            continue;
+        };
+
+        for removed in [idx - 1, idx] {
+            if first_violation(report, policy, ordering, Some(removed)).is_none() {
+                return Verdict::Dampened { removed, violation };
+            }
+        }
+    }
+
+    Verdict::Unsafe(violation)
+}
+
+/// Checks that the report levels follow the `policy`, see [`check_report`].
+pub fn is_safe_report(report: &[u32], policy: &SafetyPolicy) -> bool {
+    check_report(report, policy).is_safe()
+}
+
+/// Checks that the report follows the `policy` with dampening, see [`check_report_dampened`].
+pub fn is_safe_report_dampened(report: &[u32], policy: &SafetyPolicy) -> bool {
+    check_report_dampened(report, policy).is_safe()
+}
+
+/// Number of reports safe under the `policy` without dampening.
+pub fn count_safe(reports: &[Vec<u32>], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|report| is_safe_report(report, policy))
+        .count()
+}
+
+/// Number of reports safe under the `policy` with dampening.
+pub fn count_safe_dampened(reports: &[Vec<u32>], policy: &SafetyPolicy) -> usize {
+    reports
+        .iter()
+        .filter(|report| is_safe_report_dampened(report, policy))
+        .count()
+}
+
+/// Parses the levels of a report on the given (1-based) `line_no`, there has to be at least one.
+pub fn parse_report(line: &str, line_no: usize) -> aoc_common::Result<Vec<u32>> {
+    let report: Vec<u32> = parse::whitespace_separated(line, line_no)?;
+
+    if report.is_empty() {
+        return Err(Error::MissingToken {
+            line: line_no,
+            column: 1,
+            expected: "level",
+        });
+    }
+
+    Ok(report)
+}
+
+pub struct Day2;
+
+impl Solution for Day2 {
+    const DAY: u8 = 2;
+    const PART1: &'static str = "Number of safe reports without dampening";
+    const PART2: &'static str = "Number of safe reports with dampening";
+
+    type Input = Vec<Vec<u32>>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        input
+            .lines()
+            .enumerate()
+            .map(|(idx, line)| parse_report(line, idx + 1))
+            .collect()
+    }
+
+    /// Number of safe reports without dampening.
+    fn part1(reports: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        Ok(count_safe(reports, &SafetyPolicy::default()))
+    }
+
+    /// Number of safe reports with dampening.
+    fn part2(reports: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        Ok(count_safe_dampened(reports, &SafetyPolicy::default()))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::cmp::Ordering;
+
+    use aoc_common::{Error, Solution};
+
+    use crate::{
+        check_report, check_report_dampened, determine_ordering, is_safe_report,
+        is_safe_report_dampened,
+        policy::{Direction, Rule, SafetyPolicy},
+        prevailing_ordering, Day2, Verdict, Violation,
+    };
+
+    #[test]
+    fn parse_rejects_malformed_reports() {
+        assert!(matches!(
+            Day2::parse("7 6 4\n\n1 2 3"),
+            Err(Error::MissingToken {
+                line: 2,
+                column: 1,
+                ..
+            })
+        ));
+        assert!(matches!(
+            Day2::parse("7 6 4\n1 -2 3"),
+            Err(Error::InvalidNumber {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+    }
+
+    #[test]
+    fn is_safe_report_works() {
+        let policy = SafetyPolicy::default();
+
+        assert!(is_safe_report(&[7, 6, 4, 2, 1], &policy));
+        assert!(!is_safe_report(&[1, 2, 7, 8, 9], &policy));
+        assert!(!is_safe_report(&[9, 7, 6, 2, 1], &policy));
+        assert!(!is_safe_report(&[1, 3, 2, 4, 5], &policy));
+        assert!(!is_safe_report(&[8, 6, 4, 4, 1], &policy));
+        assert!(is_safe_report(&[1, 3, 6, 7, 9], &policy));
+
+        assert!(!is_safe_report(&[1, 1, 2, 3, 4], &policy));
+        assert!(!is_safe_report(&[71, 69, 70, 71, 72, 75], &policy));
+        assert!(!is_safe_report(&[2, 5, 4, 3, 2], &policy));
+    }
+
+    #[test]
+    fn is_safe_report_dampened_works() {
+        let policy = SafetyPolicy::default();
+
+        assert!(is_safe_report_dampened(&[7, 6, 4, 2, 1], &policy));
+        assert!(!is_safe_report_dampened(&[1, 2, 7, 8, 9], &policy));
+        assert!(!is_safe_report_dampened(&[9, 7, 6, 2, 1], &policy));
+        assert!(is_safe_report_dampened(&[1, 3, 2, 4, 5], &policy));
+        assert!(is_safe_report_dampened(&[8, 6, 4, 4, 1], &policy));
+        assert!(is_safe_report_dampened(&[1, 3, 6, 7, 9], &policy));
+
+        assert!(!is_safe_report_dampened(&[61, 64, 67, 66, 68, 67], &policy));
+        assert!(is_safe_report_dampened(
+            &[75, 72, 71, 68, 66, 65, 62, 56],
+            &policy
+        ));
+        assert!(is_safe_report_dampened(&[1, 1, 2, 3, 4], &policy));
+        assert!(is_safe_report_dampened(&[2, 5, 4, 3, 2], &policy));
+        assert!(is_safe_report_dampened(&[71, 69, 70, 71, 72, 75], &policy));
+        assert!(is_safe_report_dampened(&[5, 8, 4, 3, 2, 1], &policy));
+    }
+
+    #[test]
+    fn is_safe_report_follows_policy() {
+        let plateaus = SafetyPolicy {
+            allow_plateaus: true,
+            ..SafetyPolicy::default()
+        };
+        assert!(is_safe_report(&[1, 1, 2, 5, 5], &plateaus));
+        assert!(is_safe_report(&[3, 3, 3], &plateaus));
+        assert!(!is_safe_report(&[1, 1, 2, 1], &plateaus));
+
+        let wide_steps = SafetyPolicy {
+            min_step: 2,
+            max_step: 5,
+            ..SafetyPolicy::default()
+        };
+        assert!(is_safe_report(&[1, 6, 8, 13], &wide_steps));
+        assert!(!is_safe_report(&[1, 6, 7, 9], &wide_steps));
+
+        let increasing = SafetyPolicy {
+            direction: Direction::Increasing,
+            ..SafetyPolicy::default()
+        };
+        assert!(is_safe_report(&[1, 3, 6, 7, 9], &increasing));
+        assert!(!is_safe_report(&[7, 6, 4, 2, 1], &increasing));
+        assert!(!is_safe_report_dampened(&[7, 6, 4, 2, 1], &increasing));
+        assert!(is_safe_report_dampened(&[7, 6, 7, 8, 9], &increasing));
+    }
+
+    #[test]
+    fn check_report_works() {
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(check_report(&[7, 6, 4, 2, 1], &policy), Verdict::Safe);
+        assert_eq!(
+            check_report(&[1, 2, 7, 8, 9], &policy),
+            Verdict::Unsafe(Violation {
+                index: 2,
+                rule: Rule::StepTooLarge
+            })
+        );
+        // as many steps up as down, blamed for the direction it follows the longest
+        assert_eq!(
+            check_report(&[1, 2, 1, 0, 5], &policy),
+            Verdict::Unsafe(Violation {
+                index: 2,
+                rule: Rule::Direction
+            })
+        );
+        assert_eq!(
+            check_report_dampened(&[1, 3, 2, 4, 5], &policy),
+            Verdict::Dampened {
+                removed: 1,
+                violation: Violation {
+                    index: 2,
+                    rule: Rule::Direction
+                }
+            }
+        );
+        assert_eq!(
+            check_report_dampened(&[8, 6, 4, 4, 1], &policy),
+            Verdict::Dampened {
+                removed: 2,
+                violation: Violation {
+                    index: 3,
+                    rule: Rule::Plateau
+                }
+            }
+        );
+        assert_eq!(
+            check_report_dampened(&[8, 6, 4, 4, 1], &policy).to_string(),
+            "safe after removing level 3, level 4 repeats the previous level"
+        );
+        assert_eq!(
+            check_report_dampened(&[8, 6, 4, 4, 1], &policy).to_json(),
+            r#"{"verdict":"dampened","removed":3,"level":4,"rule":"plateau"}"#
+        );
+    }
+
+    #[test]
+    fn determine_ordering_works() {
+        assert_eq!(determine_ordering(&[1, 2, 3, 4, 5]), Ordering::Less);
+        assert_eq!(determine_ordering(&[5, 4, 3, 2, 1]), Ordering::Greater);
+        assert_eq!(determine_ordering(&[1, 1, 1, 1, 1]), Ordering::Equal);
+        assert_eq!(determine_ordering(&[1, 2, 3, 2, 1, 0]), Ordering::Greater);
+        assert_eq!(determine_ordering(&[1, 2, 1, 0, 5]), Ordering::Less);
+        assert_eq!(determine_ordering(&[4]), Ordering::Less);
+    }
+
+    #[test]
+    fn prevailing_ordering_works() {
+        assert_eq!(
+            prevailing_ordering(&[1, 2, 3, 2, 1, 0]),
+            Some(Ordering::Greater)
+        );
+        assert_eq!(prevailing_ordering(&[1, 1, 2]), None);
+        assert_eq!(prevailing_ordering(&[1, 2, 1, 0, 5]), None);
+        assert_eq!(prevailing_ordering(&[4]), None);
+    }
+}
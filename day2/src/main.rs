@@ -1,165 +1,261 @@
-use std::{
-    cmp::{max, Ordering},
-    collections::HashMap,
-    fs::File,
-    io::{BufRead, BufReader},
+use std::{fs, process::ExitCode, time::Instant};
+
+use aoc_common::{
+    cli::{self, Args, Format, Options},
+    report::{self, Report},
+    Error, Solution,
+};
+use day2::{
+    check_report_dampened, count_safe, count_safe_dampened,
+    dampener::count_safe_by_removals,
+    is_safe_report,
+    policy::SafetyPolicy,
+    repair::{edit_levels, remove_levels},
+    stats::Statistics,
+    stream::{self, Totals},
+    Day2, Verdict,
 };
 
-fn determine_ordering(report: &[u32]) -> Ordering {
-    let counts = report.windows(2).fold(
-        HashMap::from([
-            (Ordering::Less, 0),
-            (Ordering::Equal, 0),
-            (Ordering::Greater, 0),
-        ]),
-        |mut acc, el| {
-            acc.entry(match (el[0], el[1]) {
-                (f, s) if f < s => Ordering::Less,
-                (f, s) if f > s => Ordering::Greater,
-                _ => Ordering::Equal,
-            })
-            .and_modify(|v| *v += 1);
-
-            acc
-        },
-    );
-
-    counts
-        .iter()
-        .max_by_key(|(_, &count)| count)
-        .map(|(&order, _)| order)
-        .unwrap()
-}
+/// Number of reports between the running totals printed by `--stream`.
+const DEFAULT_EVERY: usize = 100;
 
-fn is_safe_report(report: &[u32]) -> bool {
-    let ordering = determine_ordering(report);
-    let mut last_level = report[0];
-
-    for &curr_level in report.iter().skip(1) {
-        if (ordering == Ordering::Less && curr_level <= last_level)
-            || (ordering == Ordering::Greater && curr_level >= last_level)
-            || ordering == Ordering::Equal
-            || !(1..=3).contains(&last_level.abs_diff(curr_level))
-        {
-            return false;
+const OPTIONS: Options = Options {
+    flags: &["--explain", "--removals", "--repair", "--stats", "--stream"],
+    values: &["--every", "--max-removals", "--policy"],
+    usage: "[--policy <path>] [--explain | --removals [--max-removals <k>] | --repair | --stats | --stream [--every <n>]]",
+};
+
+fn policy(args: &Args) -> Result<SafetyPolicy, String> {
+    match args.value::<String>("--policy")? {
+        None => Ok(SafetyPolicy::default()),
+        Some(path) => {
+            let content = fs::read_to_string(&path)
+                .map_err(|e| format!("Failed to read policy {}: {}", path, e))?;
+
+            SafetyPolicy::from_toml(&content).map_err(|e| format!("{}: {}", path, e))
         }
+    }
+}
+
+/// Prints the verdict on the report at the 0-based index `idx`.
+fn print_verdict(format: Format, idx: usize, report: &[u32], verdict: &Verdict) {
+    let levels: Vec<_> = report.iter().map(u32::to_string).collect();
 
-        last_level = curr_level;
+    match format {
+        Format::Text => println!("{}: {}: {}", idx + 1, levels.join(" "), verdict),
+        Format::Json => println!(
+            "{{\"report\":{},\"levels\":[{}],\"check\":{}}}",
+            idx + 1,
+            levels.join(","),
+            verdict.to_json()
+        ),
     }
+}
 
-    true
+/// Prints the verdict on every report, checked with dampening.
+fn explain(args: &Args, policy: &SafetyPolicy) -> ExitCode {
+    cli::with_input(args, |input| {
+        for (idx, report) in Day2::parse(input)?.iter().enumerate() {
+            print_verdict(
+                args.format,
+                idx,
+                report,
+                &check_report_dampened(report, policy),
+            );
+        }
+
+        Ok(())
+    })
 }
 
-fn is_safe_report_dampened(report: &[u32]) -> bool {
-    let ordering = determine_ordering(report);
-    let mut last_level = report[0];
+/// Prints the fewest removals and edits making each of the unsafe reports safe.
+fn repair(args: &Args, policy: &SafetyPolicy) -> ExitCode {
+    cli::with_input(args, |input| {
+        for (idx, report) in Day2::parse(input)?.iter().enumerate() {
+            if is_safe_report(report, policy) {
+                continue;
+            }
+
+            let (removed, edited) = (remove_levels(report, policy), edit_levels(report, policy));
+            let levels: Vec<_> = report.iter().map(u32::to_string).collect();
 
-    for (idx, &curr_level) in report.iter().enumerate().skip(1) {
-        let diff = last_level.abs_diff(curr_level);
+            match args.format {
+                Format::Text => {
+                    println!("{}: {}: {}; {}", idx + 1, levels.join(" "), removed, edited)
+                }
+                Format::Json => println!(
+                    "{{\"report\":{},\"levels\":[{}],\"repairs\":[{},{}]}}",
+                    idx + 1,
+                    levels.join(","),
+                    removed.to_json(),
+                    edited.to_json()
+                ),
+            }
+        }
 
-        if (ordering == Ordering::Less && curr_level <= last_level)
-            || (ordering == Ordering::Greater && curr_level >= last_level)
-            || ordering == Ordering::Equal
-            || !(1..=3).contains(&diff)
-        {
-            // println!("Unsafe without dampening: {:?}", report);
+        Ok(())
+    })
+}
 
-            let try_splits = [max(0, idx - 1), idx];
+/// Prints the number of reports safe after removing at most K levels, for every K.
+fn removals(args: &Args, policy: &SafetyPolicy) -> ExitCode {
+    let max_removals = match args.value::<usize>("--max-removals") {
+        Ok(max_removals) => max_removals,
+        Err(e) => return cli::usage_error::<Day2>(&e, &OPTIONS),
+    };
 
-            for split in try_splits {
-                let try_fixed_report = [&report[0..split], &report[split + 1..]].concat();
-                let is_safe_now = is_safe_report(&try_fixed_report);
+    cli::with_input(args, |input| {
+        let mut counts = count_safe_by_removals(&Day2::parse(input)?, policy);
+        if let Some(max_removals) = max_removals {
+            counts.truncate(max_removals + 1);
+        }
 
-                if is_safe_now {
-                    // println!("     But dampening helps: {:?}", try_fixed_report);
-                    return is_safe_now;
+        match args.format {
+            Format::Text => {
+                println!("{:>8}{:>12}", "removals", "safe");
+                for (k, count) in counts.iter().enumerate() {
+                    println!("{:>8}{:>12}", k, count);
                 }
             }
-
-            return false;
+            Format::Json => {
+                let counts: Vec<_> = counts.iter().map(usize::to_string).collect();
+                println!("{{\"day\":2,\"safe_by_removals\":[{}]}}", counts.join(","));
+            }
         }
 
-        last_level = curr_level;
-    }
+        Ok(())
+    })
+}
+
+/// Prints histograms of the reports and of how they fail the policy.
+fn stats(args: &Args, policy: &SafetyPolicy) -> ExitCode {
+    cli::with_input(args, |input| {
+        let stats = Statistics::new(&Day2::parse(input)?, policy);
+
+        match args.format {
+            Format::Text => print!("{}", stats.to_table()),
+            Format::Json => println!("{}", stats.to_json()),
+        }
 
-    true
+        Ok(())
+    })
 }
 
-fn main() {
-    let input_file = File::open("input.txt").expect("Failed to open file.");
-    let reader = BufReader::new(input_file);
-
-    let mut total_reports_count = 0;
-    let mut safe_reports_count = 0;
-    let mut safe_reports_count_dampened = 0;
-    for line in reader.lines() {
-        let report: Vec<u32> = line
-            .expect("Failed to read report line.")
-            .split_whitespace()
-            .map(|el| el.parse::<u32>().unwrap())
-            .collect();
-
-        let is_safe = is_safe_report(&report);
-        let is_safe_dampened = is_safe_report_dampened(&report);
-
-        safe_reports_count += is_safe as u32;
-        safe_reports_count_dampened += is_safe_dampened as u32;
-        total_reports_count += 1;
+/// Prints the error on the line at the 0-based index `idx` in place of a verdict.
+fn print_malformed(format: Format, idx: usize, e: &Error) {
+    let column = e.position().map_or(1, |(_, column)| column);
+
+    match format {
+        Format::Text => println!("{}: malformed, column {}: {}", idx + 1, column, e.message()),
+        Format::Json => println!(
+            "{{\"report\":{},\"check\":{{\"verdict\":\"malformed\",\"column\":{},\"error\":{}}}}}",
+            idx + 1,
+            column,
+            report::json_string(&e.message())
+        ),
     }
+}
 
-    println!("Total reports count: {}", total_reports_count);
-    println!(
-        "Number of safe reports without dampening: {}",
-        safe_reports_count
-    );
-    println!(
-        "Number of safe reports with dampening: {}",
-        safe_reports_count_dampened
-    );
+fn print_totals(format: Format, totals: &Totals) {
+    match format {
+        Format::Text => println!("{}", totals),
+        Format::Json => println!("{}", totals.to_json()),
+    }
 }
 
-#[cfg(test)]
-mod test {
-    use std::cmp::Ordering;
+/// Prints the verdict on every report as soon as it is read, checked with dampening, and the
+/// running totals after every `--every` reports and at the end of the input. Malformed lines
+/// get an error in place of the verdict, only failing to read the input stops the stream.
+fn stream(args: &Args, policy: &SafetyPolicy) -> ExitCode {
+    let every = match args.value::<usize>("--every") {
+        Ok(Some(0)) => return cli::usage_error::<Day2>("--every must be positive.", &OPTIONS),
+        Ok(every) => every.unwrap_or(DEFAULT_EVERY),
+        Err(e) => return cli::usage_error::<Day2>(&e, &OPTIONS),
+    };
 
-    use crate::{determine_ordering, is_safe_report, is_safe_report_dampened};
+    cli::with_source(args, |source| {
+        let mut totals = Totals::default();
 
-    #[test]
-    fn is_safe_report_works() {
-        assert!(is_safe_report(&[7, 6, 4, 2, 1]));
-        assert!(!is_safe_report(&[1, 2, 7, 8, 9]));
-        assert!(!is_safe_report(&[9, 7, 6, 2, 1]));
-        assert!(!is_safe_report(&[1, 3, 2, 4, 5]));
-        assert!(!is_safe_report(&[8, 6, 4, 4, 1]));
-        assert!(is_safe_report(&[1, 3, 6, 7, 9]));
+        for (idx, report) in stream::reports(source.open()?).enumerate() {
+            match report? {
+                Ok(report) => {
+                    let verdict = check_report_dampened(&report, policy);
 
-        assert!(!is_safe_report(&[1, 1, 2, 3, 4]));
-        assert!(!is_safe_report(&[71, 69, 70, 71, 72, 75]));
-        assert!(!is_safe_report(&[2, 5, 4, 3, 2]));
-    }
+                    totals.add(&verdict);
+                    print_verdict(args.format, idx, &report, &verdict);
+                }
+                Err(e) => {
+                    totals.add_malformed();
+                    print_malformed(args.format, idx, &e);
+                }
+            }
+            if totals.reports.is_multiple_of(every) {
+                print_totals(args.format, &totals);
+            }
+        }
 
-    #[test]
-    fn is_safe_report_dampened_works() {
-        assert!(is_safe_report_dampened(&[7, 6, 4, 2, 1]));
-        assert!(!is_safe_report_dampened(&[1, 2, 7, 8, 9]));
-        assert!(!is_safe_report_dampened(&[9, 7, 6, 2, 1]));
-        assert!(is_safe_report_dampened(&[1, 3, 2, 4, 5]));
-        assert!(is_safe_report_dampened(&[8, 6, 4, 4, 1]));
-        assert!(is_safe_report_dampened(&[1, 3, 6, 7, 9]));
-
-        assert!(!is_safe_report_dampened(&[61, 64, 67, 66, 68, 67]));
-        assert!(is_safe_report_dampened(&[75, 72, 71, 68, 66, 65, 62, 56]));
-        assert!(is_safe_report_dampened(&[1, 1, 2, 3, 4]));
-        assert!(is_safe_report_dampened(&[2, 5, 4, 3, 2]));
-        assert!(is_safe_report_dampened(&[71, 69, 70, 71, 72, 75]));
-        assert!(is_safe_report_dampened(&[5, 8, 4, 3, 2, 1]));
-    }
+        if !totals.reports.is_multiple_of(every) {
+            print_totals(args.format, &totals);
+        }
+
+        Ok(())
+    })
+}
 
-    #[test]
-    fn determine_ordering_works() {
-        assert_eq!(determine_ordering(&[1, 2, 3, 4, 5]), Ordering::Less);
-        assert_eq!(determine_ordering(&[5, 4, 3, 2, 1]), Ordering::Greater);
-        assert_eq!(determine_ordering(&[1, 1, 1, 1, 1]), Ordering::Equal);
-        assert_eq!(determine_ordering(&[1, 2, 3, 2, 1, 0]), Ordering::Greater);
+fn main() -> ExitCode {
+    let mut args = match cli::args::<Day2>(&OPTIONS) {
+        Ok(args) => args,
+        Err(code) => return code,
+    };
+
+    let policy = match policy(&args) {
+        Ok(policy) => policy,
+        Err(e) => return cli::usage_error::<Day2>(&e, &OPTIONS),
+    };
+
+    let modes = ["--explain", "--removals", "--repair", "--stats", "--stream"];
+    if modes.iter().filter(|&&mode| args.flag(mode)).count() > 1 {
+        return cli::usage_error::<Day2>(
+            "Only one of --explain, --removals, --repair, --stats and --stream can be given.",
+            &OPTIONS,
+        );
+    }
+    if args.flag("--explain") {
+        return explain(&args, &policy);
+    }
+    if args.flag("--removals") {
+        return removals(&args, &policy);
+    }
+    if args.flag("--repair") {
+        return repair(&args, &policy);
+    }
+    if args.flag("--stats") {
+        return stats(&args, &policy);
     }
+    if args.flag("--stream") {
+        // a live stream is piped in unless a path is given
+        args.input.get_or_insert_with(|| "-".to_string());
+        return stream(&args, &policy);
+    }
+    if args.flag("--max-removals") {
+        return cli::usage_error::<Day2>("--max-removals requires --removals.", &OPTIONS);
+    }
+    if args.flag("--every") {
+        return cli::usage_error::<Day2>("--every requires --stream.", &OPTIONS);
+    }
+
+    cli::with_input(&args, |input| {
+        let started = Instant::now();
+        let reports = Day2::parse(input)?;
+
+        let report = Report {
+            day: Day2::DAY,
+            part1: Some(count_safe(&reports, &policy).to_string()),
+            part2: Some(count_safe_dampened(&reports, &policy).to_string()),
+            elapsed: started.elapsed(),
+        };
+        cli::print::<Day2>(&report, args.format);
+
+        Ok(())
+    })
 }
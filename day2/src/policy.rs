@@ -0,0 +1,195 @@
+//! Tolerances the reports are checked against.
+
+use std::{cmp::Ordering, fmt, str::FromStr};
+
+use toml::{Table, Value};
+
+/// Direction the levels of a safe report have to go in.
+#[derive(Debug, Clone, Copy, PartialEq, Default)]
+pub enum Direction {
+    /// Either way, as long as all the levels follow the prevailing direction of the report.
+    #[default]
+    Any,
+    Increasing,
+    Decreasing,
+}
+
+impl FromStr for Direction {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "any" => Ok(Direction::Any),
+            "increasing" => Ok(Direction::Increasing),
+            "decreasing" => Ok(Direction::Decreasing),
+            _ => Err(format!("Invalid direction: {}", s)),
+        }
+    }
+}
+
+impl fmt::Display for Direction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Direction::Any => write!(f, "any"),
+            Direction::Increasing => write!(f, "increasing"),
+            Direction::Decreasing => write!(f, "decreasing"),
+        }
+    }
+}
+
+/// Rule of a [`SafetyPolicy`] a level can break.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Rule {
+    /// The level goes against the direction of the report.
+    Direction,
+    /// The level equals the previous one while plateaus are not allowed.
+    Plateau,
+    StepTooSmall,
+    StepTooLarge,
+}
+
+impl Rule {
+    /// Identifier of the rule in machine-readable output.
+    pub fn code(&self) -> &'static str {
+        match self {
+            Rule::Direction => "direction",
+            Rule::Plateau => "plateau",
+            Rule::StepTooSmall => "step_too_small",
+            Rule::StepTooLarge => "step_too_large",
+        }
+    }
+}
+
+impl fmt::Display for Rule {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Rule::Direction => write!(f, "goes against the direction of the report"),
+            Rule::Plateau => write!(f, "repeats the previous level"),
+            Rule::StepTooSmall => write!(f, "differs from the previous level too little"),
+            Rule::StepTooLarge => write!(f, "differs from the previous level too much"),
+        }
+    }
+}
+
+/// Rules a report has to follow to be safe, the default ones being those of the puzzle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct SafetyPolicy {
+    /// Smallest allowed difference between adjacent levels, at least 1.
+    pub min_step: u32,
+    /// Largest allowed difference between adjacent levels.
+    pub max_step: u32,
+    /// Whether adjacent levels may be equal, regardless of the allowed steps.
+    pub allow_plateaus: bool,
+    pub direction: Direction,
+}
+
+impl Default for SafetyPolicy {
+    fn default() -> Self {
+        Self {
+            min_step: 1,
+            max_step: 3,
+            allow_plateaus: false,
+            direction: Direction::Any,
+        }
+    }
+}
+
+impl SafetyPolicy {
+    /// Reads the policy from a TOML document like the following, missing keys keep their
+    /// default values:
+    ///
+    /// ```toml
+    /// min_step = 1
+    /// max_step = 3
+    /// allow_plateaus = false
+    /// direction = "any" # or "increasing", "decreasing"
+    /// ```
+    pub fn from_toml(content: &str) -> Result<Self, String> {
+        let table: Table = content
+            .parse()
+            .map_err(|e| format!("Failed to parse policy: {}", e))?;
+
+        let mut policy = SafetyPolicy::default();
+        for (key, value) in &table {
+            match (key.as_str(), value) {
+                ("min_step", Value::Integer(step)) => policy.min_step = step_value(key, *step)?,
+                ("max_step", Value::Integer(step)) => policy.max_step = step_value(key, *step)?,
+                ("allow_plateaus", Value::Boolean(allow)) => policy.allow_plateaus = *allow,
+                ("direction", Value::String(direction)) => policy.direction = direction.parse()?,
+                ("min_step" | "max_step" | "allow_plateaus" | "direction", v) => {
+                    return Err(format!("{} has unexpected type {}", key, v.type_str()))
+                }
+                _ => return Err(format!("Unexpected key in policy: {}", key)),
+            }
+        }
+
+        if policy.min_step == 0 {
+            return Err("min_step must be at least 1, allow plateaus instead".to_string());
+        }
+        if policy.min_step > policy.max_step {
+            return Err("min_step must not exceed max_step".to_string());
+        }
+
+        Ok(policy)
+    }
+
+    /// Directions a safe report may go in, `Less` meaning increasing and `Greater` decreasing.
+    pub fn orderings(&self) -> &'static [Ordering] {
+        match self.direction {
+            Direction::Any => &[Ordering::Less, Ordering::Greater],
+            Direction::Increasing => &[Ordering::Less],
+            Direction::Decreasing => &[Ordering::Greater],
+        }
+    }
+
+    /// Rule of the policy the step from `last_level` to `curr_level` breaks in a report going in
+    /// the given `ordering`, if any.
+    pub fn violation(&self, ordering: Ordering, last_level: u32, curr_level: u32) -> Option<Rule> {
+        if curr_level == last_level {
+            return (!self.allow_plateaus).then_some(Rule::Plateau);
+        }
+
+        let step = last_level.abs_diff(curr_level);
+        if curr_level.cmp(&last_level) != ordering.reverse() {
+            Some(Rule::Direction)
+        } else if step < self.min_step {
+            Some(Rule::StepTooSmall)
+        } else if step > self.max_step {
+            Some(Rule::StepTooLarge)
+        } else {
+            None
+        }
+    }
+}
+
+fn step_value(key: &str, step: i64) -> Result<u32, String> {
+    u32::try_from(step).map_err(|_| format!("{} must be a non-negative integer", key))
+}
+
+#[cfg(test)]
+mod test {
+    use crate::policy::{Direction, SafetyPolicy};
+
+    #[test]
+    fn from_toml_works() {
+        assert_eq!(
+            SafetyPolicy::from_toml("max_step = 5\ndirection = \"decreasing\"\n"),
+            Ok(SafetyPolicy {
+                max_step: 5,
+                direction: Direction::Decreasing,
+                ..SafetyPolicy::default()
+            })
+        );
+        assert_eq!(SafetyPolicy::from_toml(""), Ok(SafetyPolicy::default()));
+    }
+
+    #[test]
+    fn from_toml_rejects_invalid() {
+        assert!(SafetyPolicy::from_toml("min_step = 0").is_err());
+        assert!(SafetyPolicy::from_toml("min_step = 4").is_err());
+        assert!(SafetyPolicy::from_toml("max_step = -1").is_err());
+        assert!(SafetyPolicy::from_toml("max_step = \"3\"").is_err());
+        assert!(SafetyPolicy::from_toml("direction = \"up\"").is_err());
+        assert!(SafetyPolicy::from_toml("tolerance = 1").is_err());
+    }
+}
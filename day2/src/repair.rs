@@ -0,0 +1,300 @@
+//! Fewest changes turning an unsafe report into a safe one.
+
+use std::{cmp::Ordering, fmt};
+
+use crate::{dampener::longest_safe_subsequence, policy::SafetyPolicy};
+
+/// Way of repairing a report.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Strategy {
+    /// Removing levels, like the Problem Dampener.
+    Remove,
+    /// Changing the values of levels, keeping the length of the report.
+    Edit,
+}
+
+impl fmt::Display for Strategy {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Strategy::Remove => write!(f, "remove"),
+            Strategy::Edit => write!(f, "edit"),
+        }
+    }
+}
+
+/// Report made safe with the fewest `changes` of the given strategy.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Repair {
+    pub strategy: Strategy,
+    /// Number of levels removed or edited.
+    pub changes: usize,
+    /// One of the safe reports the changes lead to.
+    pub report: Vec<u32>,
+}
+
+impl Repair {
+    /// Formats the repair as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        let levels: Vec<_> = self.report.iter().map(u32::to_string).collect();
+
+        format!(
+            "{{\"strategy\":\"{}\",\"changes\":{},\"report\":[{}]}}",
+            self.strategy,
+            self.changes,
+            levels.join(",")
+        )
+    }
+}
+
+impl fmt::Display for Repair {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let levels: Vec<_> = self.report.iter().map(u32::to_string).collect();
+
+        write!(
+            f,
+            "{} {} level{}: {}",
+            self.strategy,
+            self.changes,
+            if self.changes == 1 { "" } else { "s" },
+            levels.join(" ")
+        )
+    }
+}
+
+/// Repairs the report by removing the fewest levels, keeping its longest safe subsequence.
+pub fn remove_levels(report: &[u32], policy: &SafetyPolicy) -> Repair {
+    let kept = longest_safe_subsequence(report, policy);
+
+    Repair {
+        strategy: Strategy::Remove,
+        changes: report.len() - kept.len(),
+        report: kept.into_iter().map(|idx| report[idx]).collect(),
+    }
+}
+
+/// Repairs the report by changing the values of the fewest levels.
+pub fn edit_levels(report: &[u32], policy: &SafetyPolicy) -> Repair {
+    let edited = policy
+        .orderings()
+        .iter()
+        .map(|&ordering| Editor::new(report, policy, ordering).edit())
+        .min_by_key(|edited| changes(report, edited))
+        .unwrap_or_default();
+
+    Repair {
+        strategy: Strategy::Edit,
+        changes: changes(report, &edited),
+        report: edited,
+    }
+}
+
+fn changes(report: &[u32], edited: &[u32]) -> usize {
+    report.iter().zip(edited).filter(|(a, b)| a != b).count()
+}
+
+/// Edits a report to go in a fixed direction, computed in `i64` so that edited levels can be
+/// checked to fit `u32`.
+struct Editor<'a> {
+    policy: &'a SafetyPolicy,
+    levels: Vec<i64>,
+    /// `1` if the report has to increase, `-1` if it has to decrease.
+    sign: i64,
+    /// Smallest step between edited levels, plateaus when allowed.
+    step: i64,
+}
+
+impl<'a> Editor<'a> {
+    fn new(report: &[u32], policy: &'a SafetyPolicy, ordering: Ordering) -> Self {
+        Self {
+            policy,
+            levels: report.iter().map(|&level| level as i64).collect(),
+            sign: if ordering == Ordering::Less { 1 } else { -1 },
+            step: if policy.allow_plateaus {
+                0
+            } else {
+                policy.min_step as i64
+            },
+        }
+    }
+
+    fn fits(level: i64) -> bool {
+        (0..=u32::MAX as i64).contains(&level)
+    }
+
+    /// Number of non-plateau steps out of `gap` ones changing a level by `diff` in the direction
+    /// of the report, the fewest possible if plateaus are allowed. `None` if it cannot be done.
+    fn climbing_steps(&self, diff: i64, gap: usize) -> Option<usize> {
+        let (min, max) = (self.policy.min_step as i64, self.policy.max_step as i64);
+        let gap = gap as i64;
+
+        let steps = match self.policy.allow_plateaus {
+            false => gap,
+            true if diff == 0 => 0,
+            true => (diff + max - 1) / max,
+        };
+
+        (steps <= gap && steps * min <= diff && diff <= steps * max).then_some(steps as usize)
+    }
+
+    /// Level `levels` positions away from `level` in the direction of the report, by the
+    /// smallest steps.
+    fn shifted(&self, level: i64, levels: usize) -> i64 {
+        level + self.sign * levels as i64 * self.step
+    }
+
+    /// Keeps the longest set of levels the others can be edited around, see [`Editor::kept`].
+    fn edit(&self) -> Vec<u32> {
+        let n = self.levels.len();
+        let kept = self.kept();
+
+        let mut edited = self.levels.clone();
+        match (kept.first(), kept.last()) {
+            (Some(&first), Some(&last)) => {
+                for (idx, level) in edited[..first].iter_mut().enumerate() {
+                    *level = self.levels[first] - self.sign * (first - idx) as i64 * self.step;
+                }
+                for pair in kept.windows(2) {
+                    self.fill(&mut edited, pair[0], pair[1]);
+                }
+                for (idx, level) in edited.iter_mut().enumerate().skip(last + 1) {
+                    *level = self.shifted(self.levels[last], idx - last);
+                }
+            }
+            _ => {
+                // no level can be kept, start from the end of the range the report goes away from
+                let start = if self.sign > 0 {
+                    0
+                } else {
+                    n.saturating_sub(1) as i64 * self.step
+                };
+                for (idx, level) in edited.iter_mut().enumerate() {
+                    *level = self.shifted(start, idx);
+                }
+            }
+        }
+
+        edited.into_iter().map(|level| level as u32).collect()
+    }
+
+    /// Indices of the most levels the others can be edited around: `edits[j]` is the fewest
+    /// edits of the levels up to `j` keeping it, extending the best of the earlier kept levels the
+    /// gap to which can be bridged.
+    fn kept(&self) -> Vec<usize> {
+        let n = self.levels.len();
+        let mut edits: Vec<Option<usize>> = vec![None; n];
+        let mut previous = vec![None; n];
+
+        for j in 0..n {
+            // the levels before can be edited to lead to this one
+            if Self::fits(self.levels[j] - self.sign * j as i64 * self.step) {
+                edits[j] = Some(j);
+            }
+
+            for i in 0..j {
+                let Some(before) = edits[i] else {
+                    continue;
+                };
+                let diff = self.sign * (self.levels[j] - self.levels[i]);
+                if diff >= 0
+                    && self.climbing_steps(diff, j - i).is_some()
+                    && edits[j].is_none_or(|e| before + j - i - 1 < e)
+                {
+                    edits[j] = Some(before + j - i - 1);
+                    previous[j] = Some(i);
+                }
+            }
+        }
+
+        // the levels after can be edited to follow this one
+        let last = (0..n)
+            .filter(|&j| Self::fits(self.shifted(self.levels[j], n - 1 - j)))
+            .filter_map(|j| edits[j].map(|e| (e + n - 1 - j, j)))
+            .min()
+            .map(|(_, j)| j);
+
+        let mut kept = Vec::new();
+        let mut current = last;
+        while let Some(j) = current {
+            kept.push(j);
+            current = previous[j];
+        }
+        kept.reverse();
+
+        kept
+    }
+
+    /// Edits the levels between the kept levels `from` and `to`.
+    fn fill(&self, edited: &mut [i64], from: usize, to: usize) {
+        let (min, max) = (self.policy.min_step as i64, self.policy.max_step as i64);
+        let diff = self.sign * (self.levels[to] - self.levels[from]);
+        let steps = self
+            .climbing_steps(diff, to - from)
+            .expect("kept levels can be bridged");
+
+        // every climbing step is at least `min`, the rest of the difference spread from the start
+        let mut rest = diff - steps as i64 * min;
+        let mut level = self.levels[from];
+        for (taken, idx) in (from + 1..to).enumerate() {
+            if taken < steps {
+                let extra = rest.min(max - min);
+                rest -= extra;
+                level += self.sign * (min + extra);
+            }
+            edited[idx] = level;
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::{
+        is_safe_report,
+        policy::SafetyPolicy,
+        repair::{edit_levels, remove_levels, Repair, Strategy},
+    };
+
+    #[test]
+    fn remove_levels_works() {
+        assert_eq!(
+            remove_levels(&[1, 2, 7, 8, 9], &SafetyPolicy::default()),
+            Repair {
+                strategy: Strategy::Remove,
+                changes: 2,
+                report: vec![7, 8, 9]
+            }
+        );
+    }
+
+    #[test]
+    fn to_json_works() {
+        let repair = remove_levels(&[1, 2, 7, 8, 9], &SafetyPolicy::default());
+
+        assert_eq!(repair.to_string(), "remove 2 levels: 7 8 9");
+        assert_eq!(
+            repair.to_json(),
+            r#"{"strategy":"remove","changes":2,"report":[7,8,9]}"#
+        );
+    }
+
+    #[test]
+    fn edit_levels_works() {
+        let policy = SafetyPolicy::default();
+
+        assert_eq!(
+            edit_levels(&[1, 2, 7, 8, 9], &policy).report,
+            vec![1, 2, 5, 8, 9]
+        );
+
+        let repair = edit_levels(&[1, 50, 2], &policy);
+        assert_eq!(repair.changes, 2);
+        assert!(is_safe_report(&repair.report, &policy));
+
+        assert_eq!(
+            edit_levels(&[1, 3, 2, 4, 5], &policy).report,
+            vec![1, 2, 3, 4, 5]
+        );
+        assert_eq!(edit_levels(&[8, 6, 4, 4, 1], &policy).changes, 1);
+        assert_eq!(edit_levels(&[7, 6, 4, 2, 1], &policy).changes, 0);
+        assert_eq!(edit_levels(&[0, 0, 0], &policy).changes, 2);
+    }
+}
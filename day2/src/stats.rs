@@ -0,0 +1,203 @@
+//! Distributions over a set of reports, to get a feel for the input.
+
+use std::{cmp::Ordering, collections::BTreeMap, fmt::Display};
+
+use crate::{
+    check_report,
+    policy::{Rule, SafetyPolicy},
+    prevailing_ordering, Verdict,
+};
+
+/// Width of the longest bar in the histograms.
+const BAR_WIDTH: usize = 40;
+
+/// Histograms of the reports and of how they fail the policy, without dampening.
+#[derive(Debug, Default, PartialEq)]
+pub struct Statistics {
+    pub reports: usize,
+    /// Number of reports by number of levels.
+    pub lengths: BTreeMap<usize, usize>,
+    /// Number of steps between adjacent levels by their size.
+    pub steps: BTreeMap<u32, usize>,
+    /// Number of reports by prevailing direction, `None` for those with fewer than two levels
+    /// or no clear direction, see [`prevailing_ordering`].
+    pub directions: BTreeMap<Option<Ordering>, usize>,
+    /// Number of unsafe reports by the rule their first violation breaks.
+    pub failures: BTreeMap<Rule, usize>,
+    /// Number of unsafe reports by the 1-based level of their first violation.
+    pub first_violations: BTreeMap<usize, usize>,
+}
+
+impl Statistics {
+    pub fn new(reports: &[Vec<u32>], policy: &SafetyPolicy) -> Self {
+        let mut stats = Statistics {
+            reports: reports.len(),
+            ..Statistics::default()
+        };
+
+        for report in reports {
+            *stats.lengths.entry(report.len()).or_default() += 1;
+            for pair in report.windows(2) {
+                *stats.steps.entry(pair[0].abs_diff(pair[1])).or_default() += 1;
+            }
+            *stats
+                .directions
+                .entry(prevailing_ordering(report))
+                .or_default() += 1;
+
+            if let Verdict::Unsafe(violation) = check_report(report, policy) {
+                *stats.failures.entry(violation.rule).or_default() += 1;
+                *stats
+                    .first_violations
+                    .entry(violation.index + 1)
+                    .or_default() += 1;
+            }
+        }
+
+        stats
+    }
+
+    /// Histograms with their titles, as labelled counts.
+    fn histograms(&self) -> [(&'static str, Vec<(String, usize)>); 5] {
+        fn labelled<K: Display>(counts: &BTreeMap<K, usize>) -> Vec<(String, usize)> {
+            counts
+                .iter()
+                .map(|(key, &count)| (key.to_string(), count))
+                .collect()
+        }
+
+        let directions = self
+            .directions
+            .iter()
+            .map(|(ordering, &count)| {
+                let label = match ordering {
+                    Some(Ordering::Less) => "increasing",
+                    Some(Ordering::Equal) => "flat",
+                    Some(Ordering::Greater) => "decreasing",
+                    None => "unclear",
+                };
+                (label.to_string(), count)
+            })
+            .collect();
+        let failures = self
+            .failures
+            .iter()
+            .map(|(rule, &count)| (rule.code().to_string(), count))
+            .collect();
+
+        [
+            ("length", labelled(&self.lengths)),
+            ("step", labelled(&self.steps)),
+            ("direction", directions),
+            ("failure", failures),
+            ("first_violation", labelled(&self.first_violations)),
+        ]
+    }
+
+    /// Formats the histograms as tables, with bars scaled to the largest count of each.
+    pub fn to_table(&self) -> String {
+        let mut table = format!("{:>16}{:>12}\n", "reports", self.reports);
+
+        for (title, counts) in self.histograms() {
+            let largest = counts.iter().map(|&(_, count)| count).max().unwrap_or(1);
+
+            table += &format!("\n{:>16}{:>12}\n", title, "reports");
+            for (label, count) in counts {
+                let bar = "#".repeat((count * BAR_WIDTH).div_ceil(largest));
+                table += &format!("{:>16}{:>12}  {}\n", label, count, bar);
+            }
+        }
+
+        table
+    }
+
+    /// Formats the histograms as a single-line JSON object, each an object of counts by label.
+    pub fn to_json(&self) -> String {
+        let histograms: Vec<_> = self
+            .histograms()
+            .into_iter()
+            .map(|(title, counts)| {
+                let counts: Vec<_> = counts
+                    .iter()
+                    .map(|(label, count)| format!("\"{}\":{}", label, count))
+                    .collect();
+                format!("\"{}\":{{{}}}", title, counts.join(","))
+            })
+            .collect();
+
+        format!(
+            "{{\"day\":2,\"reports\":{},{}}}",
+            self.reports,
+            histograms.join(",")
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{cmp::Ordering, collections::BTreeMap};
+
+    use aoc_common::Solution;
+
+    use crate::{
+        policy::{Rule, SafetyPolicy},
+        stats::Statistics,
+        Day2,
+    };
+
+    const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    #[test]
+    fn new_works() {
+        let reports = Day2::parse(EXAMPLE).unwrap();
+        let stats = Statistics::new(&reports, &SafetyPolicy::default());
+
+        assert_eq!(stats.reports, 6);
+        assert_eq!(stats.lengths, BTreeMap::from([(5, 6)]));
+        assert_eq!(
+            stats.steps,
+            BTreeMap::from([(0, 1), (1, 10), (2, 9), (3, 2), (4, 1), (5, 1)])
+        );
+        assert_eq!(
+            stats.directions,
+            BTreeMap::from([(Some(Ordering::Less), 3), (Some(Ordering::Greater), 3)])
+        );
+        assert_eq!(
+            stats.failures,
+            BTreeMap::from([
+                (Rule::Direction, 1),
+                (Rule::Plateau, 1),
+                (Rule::StepTooLarge, 2)
+            ])
+        );
+        assert_eq!(stats.first_violations, BTreeMap::from([(3, 2), (4, 2)]));
+    }
+
+    #[test]
+    fn new_buckets_unclear_directions() {
+        let reports = Day2::parse("3 5 4 9 8\n4\n7\n1 1 2 2\n").unwrap();
+        let stats = Statistics::new(&reports, &SafetyPolicy::default());
+
+        // steps equally often up and down, single levels and mostly plateaus
+        assert_eq!(
+            stats.directions,
+            BTreeMap::from([(None, 3), (Some(Ordering::Equal), 1)])
+        );
+        assert_eq!(stats.first_violations, BTreeMap::from([(2, 1), (3, 1)]));
+    }
+
+    #[test]
+    fn to_json_works() {
+        let reports = Day2::parse("1 2 3\n1 5 6 7\n").unwrap();
+        let stats = Statistics::new(&reports, &SafetyPolicy::default());
+
+        assert_eq!(
+            stats.to_json(),
+            concat!(
+                r#"{"day":2,"reports":2,"length":{"3":1,"4":1},"step":{"1":4,"4":1},"#,
+                r#""direction":{"increasing":2},"failure":{"step_too_large":1},"#,
+                r#""first_violation":{"2":1}}"#
+            )
+        );
+    }
+}
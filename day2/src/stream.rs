@@ -0,0 +1,133 @@
+//! Checking reports as they arrive, e.g. from a pipe fed by a live process.
+
+use std::{
+    fmt,
+    io::{self, BufRead},
+};
+
+use crate::{parse_report, Verdict};
+
+/// Reports read line by line from `reader`, each parsed as soon as its line is complete.
+///
+/// Only failing to read a line ends the stream, a malformed line is passed on as its parse error.
+pub fn reports(
+    reader: impl BufRead,
+) -> impl Iterator<Item = io::Result<aoc_common::Result<Vec<u32>>>> {
+    reader
+        .lines()
+        .enumerate()
+        .map(|(idx, line)| line.map(|line| parse_report(&line, idx + 1)))
+}
+
+/// Running totals of the reports checked with dampening so far.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub struct Totals {
+    /// Lines read, malformed ones included.
+    pub reports: usize,
+    /// Reports safe without dampening.
+    pub safe: usize,
+    /// Reports safe with dampening, including those safe without it.
+    pub safe_dampened: usize,
+    /// Lines that could not be parsed as reports.
+    pub malformed: usize,
+}
+
+impl Totals {
+    /// Counts the verdict on another report, see [`crate::check_report_dampened`].
+    pub fn add(&mut self, verdict: &Verdict) {
+        self.reports += 1;
+        match verdict {
+            Verdict::Safe => {
+                self.safe += 1;
+                self.safe_dampened += 1;
+            }
+            Verdict::Dampened { .. } => self.safe_dampened += 1,
+            Verdict::Unsafe(_) => {}
+        }
+    }
+
+    /// Counts a line that could not be parsed as a report.
+    pub fn add_malformed(&mut self) {
+        self.reports += 1;
+        self.malformed += 1;
+    }
+
+    /// Formats the totals as a single-line JSON object.
+    pub fn to_json(&self) -> String {
+        format!(
+            "{{\"reports\":{},\"safe\":{},\"safe_dampened\":{},\"malformed\":{}}}",
+            self.reports, self.safe, self.safe_dampened, self.malformed
+        )
+    }
+}
+
+impl fmt::Display for Totals {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "after {} reports: {} safe, {} safe with dampening, {} malformed",
+            self.reports, self.safe, self.safe_dampened, self.malformed
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::io::Cursor;
+
+    use aoc_common::Error;
+
+    use crate::{
+        check_report_dampened,
+        policy::SafetyPolicy,
+        stream::{reports, Totals},
+    };
+
+    const EXAMPLE: &str = "7 6 4 2 1\n1 2 7 8 9\n9 7 6 2 1\n1 3 2 4 5\n8 6 4 4 1\n1 3 6 7 9\n";
+
+    #[test]
+    fn totals_work() {
+        let policy = SafetyPolicy::default();
+
+        let mut totals = Totals::default();
+        for report in reports(Cursor::new(EXAMPLE)) {
+            totals.add(&check_report_dampened(&report.unwrap().unwrap(), &policy));
+        }
+        totals.add_malformed();
+
+        assert_eq!(
+            totals,
+            Totals {
+                reports: 7,
+                safe: 2,
+                safe_dampened: 4,
+                malformed: 1
+            }
+        );
+        assert_eq!(
+            totals.to_string(),
+            "after 7 reports: 2 safe, 4 safe with dampening, 1 malformed"
+        );
+        assert_eq!(
+            totals.to_json(),
+            r#"{"reports":7,"safe":2,"safe_dampened":4,"malformed":1}"#
+        );
+    }
+
+    #[test]
+    fn reports_continue_past_malformed_lines() {
+        let mut reports = reports(Cursor::new("7 6 4\n1 x 3\n\n4 5\n"));
+
+        assert_eq!(reports.next().unwrap().unwrap().unwrap(), vec![7, 6, 4]);
+        assert!(matches!(
+            reports.next().unwrap().unwrap(),
+            Err(Error::InvalidNumber { line: 2, .. })
+        ));
+        assert!(matches!(
+            reports.next().unwrap().unwrap(),
+            Err(Error::MissingToken { line: 3, .. })
+        ));
+        assert_eq!(reports.next().unwrap().unwrap().unwrap(), vec![4, 5]);
+        assert!(reports.next().is_none());
+    }
+}
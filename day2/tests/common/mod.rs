@@ -0,0 +1,54 @@
+//! Random reports shared by the property tests.
+
+use aoc_common::rng::Rng;
+use day2::policy::{Direction, SafetyPolicy};
+
+/// Report of 1 to 8 levels, mostly going in one direction by small steps so that many of them are
+/// safe or one level away from it.
+pub fn random_report(rng: &mut Rng) -> Vec<u32> {
+    let len = rng.range(1..=8) as usize;
+    let increasing = rng.chance(0.5);
+
+    let mut level = rng.range(30..=40) as u32;
+    let mut report = vec![level];
+    for _ in 1..len {
+        let step = rng.range(0..=4) as u32;
+        level = if increasing ^ rng.chance(0.15) {
+            level + step
+        } else {
+            level - step
+        };
+        report.push(level);
+    }
+
+    if rng.chance(0.3) {
+        let idx = rng.below(len as u64) as usize;
+        report[idx] = rng.range(20..=50) as u32;
+    }
+
+    report
+}
+
+pub fn policies() -> Vec<SafetyPolicy> {
+    vec![
+        SafetyPolicy::default(),
+        SafetyPolicy {
+            allow_plateaus: true,
+            ..SafetyPolicy::default()
+        },
+        SafetyPolicy {
+            min_step: 2,
+            max_step: 4,
+            ..SafetyPolicy::default()
+        },
+        SafetyPolicy {
+            direction: Direction::Increasing,
+            ..SafetyPolicy::default()
+        },
+        SafetyPolicy {
+            direction: Direction::Decreasing,
+            allow_plateaus: true,
+            ..SafetyPolicy::default()
+        },
+    ]
+}
@@ -0,0 +1,103 @@
+//! Compares the Problem Dampener with trying to remove every level in turn, over random reports.
+
+use aoc_common::rng::Rng;
+use common::{policies, random_report};
+use day2::{
+    check_report_dampened, dampener::min_removals, is_safe_report, is_safe_report_dampened,
+    policy::SafetyPolicy, Verdict,
+};
+
+mod common;
+
+const REPORTS: usize = 10_000;
+
+fn without(report: &[u32], idx: usize) -> Vec<u32> {
+    [&report[..idx], &report[idx + 1..]].concat()
+}
+
+/// Safe as is or after removing any single level.
+fn oracle(report: &[u32], policy: &SafetyPolicy) -> bool {
+    is_safe_report(report, policy)
+        || (0..report.len()).any(|idx| is_safe_report(&without(report, idx), policy))
+}
+
+#[test]
+fn dampened_matches_oracle() {
+    for (seed, policy) in policies().iter().enumerate() {
+        let mut rng = Rng::new(seed as u64);
+
+        for _ in 0..REPORTS {
+            let report = random_report(&mut rng);
+
+            assert_eq!(
+                is_safe_report_dampened(&report, policy),
+                oracle(&report, policy),
+                "{:?} under {:?}",
+                report,
+                policy
+            );
+        }
+    }
+}
+
+#[test]
+fn dampened_removes_fixing_level() {
+    for (seed, policy) in policies().iter().enumerate() {
+        let mut rng = Rng::new(seed as u64);
+
+        for _ in 0..REPORTS {
+            let report = random_report(&mut rng);
+
+            if let Verdict::Dampened { removed, .. } = check_report_dampened(&report, policy) {
+                assert!(!is_safe_report(&report, policy), "{:?}", report);
+                assert!(
+                    is_safe_report(&without(&report, removed), policy),
+                    "{:?} without level {} under {:?}",
+                    report,
+                    removed,
+                    policy
+                );
+            }
+        }
+    }
+}
+
+#[test]
+fn min_removals_matches_oracle() {
+    for (seed, policy) in policies().iter().enumerate() {
+        let mut rng = Rng::new(seed as u64);
+
+        for _ in 0..REPORTS {
+            let report = random_report(&mut rng);
+
+            assert_eq!(
+                min_removals(&report, policy) <= 1,
+                oracle(&report, policy),
+                "{:?} under {:?}",
+                report,
+                policy
+            );
+            assert_eq!(
+                min_removals(&report, policy) == 0,
+                is_safe_report(&report, policy),
+                "{:?} under {:?}",
+                report,
+                policy
+            );
+        }
+    }
+}
+
+#[test]
+fn edge_cases_work() {
+    let policy = SafetyPolicy::default();
+
+    // no prevailing direction, the last level has to go
+    assert!(is_safe_report_dampened(&[30, 28, 50], &policy));
+
+    assert!(is_safe_report_dampened(&[9, 1, 2, 3], &policy));
+    assert!(is_safe_report_dampened(&[1, 9, 8, 7], &policy));
+    assert!(is_safe_report_dampened(&[5, 5, 6], &policy));
+    assert!(is_safe_report_dampened(&[1, 3, 2], &policy));
+    assert!(!is_safe_report_dampened(&[9, 1, 2, 9], &policy));
+}
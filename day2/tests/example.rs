@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day2::Day2;
+
+const EXAMPLE: &str = include_str!("fixtures/example.txt");
+
+#[test]
+fn part1_example() {
+    let reports = Day2::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day2::part1(&reports).unwrap(), 2);
+}
+
+#[test]
+fn part2_example() {
+    let reports = Day2::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day2::part2(&reports).unwrap(), 4);
+}
@@ -0,0 +1,75 @@
+//! Checks the repairs of random reports, editing single levels in turn for comparison.
+
+use aoc_common::rng::Rng;
+use common::{policies, random_report};
+use day2::{
+    dampener::min_removals,
+    is_safe_report,
+    policy::SafetyPolicy,
+    repair::{edit_levels, remove_levels},
+};
+
+mod common;
+
+const REPORTS: usize = 1_000;
+
+/// Safe after changing a single level to any value close to the others.
+fn oracle(report: &[u32], policy: &SafetyPolicy) -> bool {
+    (0..report.len()).any(|idx| {
+        (0..=80).any(|level| {
+            let mut edited = report.to_vec();
+            edited[idx] = level;
+
+            is_safe_report(&edited, policy)
+        })
+    })
+}
+
+#[test]
+fn repairs_are_safe() {
+    for (seed, policy) in policies().iter().enumerate() {
+        let mut rng = Rng::new(seed as u64);
+
+        for _ in 0..REPORTS {
+            let report = random_report(&mut rng);
+
+            let removed = remove_levels(&report, policy);
+            assert_eq!(removed.changes, min_removals(&report, policy));
+            assert!(is_safe_report(&removed.report, policy), "{:?}", removed);
+
+            let edited = edit_levels(&report, policy);
+            assert_eq!(edited.report.len(), report.len());
+            assert_eq!(
+                edited.changes,
+                report
+                    .iter()
+                    .zip(&edited.report)
+                    .filter(|(a, b)| a != b)
+                    .count()
+            );
+            assert!(is_safe_report(&edited.report, policy), "{:?}", edited);
+        }
+    }
+}
+
+#[test]
+fn single_edits_match_oracle() {
+    for (seed, policy) in policies().iter().enumerate() {
+        let mut rng = Rng::new(seed as u64);
+
+        for _ in 0..REPORTS {
+            let report = random_report(&mut rng);
+            let edited = edit_levels(&report, policy);
+
+            assert_eq!(edited.changes == 0, is_safe_report(&report, policy));
+            assert_eq!(
+                edited.changes <= 1,
+                oracle(&report, policy),
+                "{:?} edited to {:?} under {:?}",
+                report,
+                edited,
+                policy
+            );
+        }
+    }
+}
@@ -0,0 +1,182 @@
+//! Tokens of the corrupted memory: the instructions hidden in it and the garbage around them.
+
+use std::{fmt, ops::Range};
+
+use crate::Instruction;
+
+/// Kind of a token of the corrupted memory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TokenKind {
+    /// `mul(X,Y)` along with its arguments.
+    Mul(u32, u32),
+    /// `do()`
+    Do,
+    /// `don't()`
+    Dont,
+    /// Longest run of memory not forming any instruction.
+    Garbage,
+}
+
+impl TokenKind {
+    /// Instruction the token stands for, `None` for garbage.
+    pub fn instruction(&self) -> Option<Instruction> {
+        match *self {
+            TokenKind::Mul(arg1, arg2) => Some(Instruction::Mul(arg1, arg2)),
+            TokenKind::Do => Some(Instruction::Do),
+            TokenKind::Dont => Some(Instruction::Dont),
+            TokenKind::Garbage => None,
+        }
+    }
+}
+
+impl fmt::Display for TokenKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.instruction() {
+            Some(instruction) => write!(f, "{}", instruction),
+            None => write!(f, "garbage"),
+        }
+    }
+}
+
+/// Token along with the byte offsets of its text in the memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Token {
+    pub kind: TokenKind,
+    pub span: Range<usize>,
+}
+
+/// Instruction token at the start of `mem` along with the length of its text, if any.
+pub fn instruction_at(mem: &str) -> Option<(TokenKind, usize)> {
+    if mem.starts_with("do()") {
+        return Some((TokenKind::Do, "do()".len()));
+    }
+    if mem.starts_with("don't()") {
+        return Some((TokenKind::Dont, "don't()".len()));
+    }
+
+    let rest = mem.strip_prefix("mul(")?;
+    let (arg1, rest) = number(rest)?;
+    let rest = rest.strip_prefix(',')?;
+    let (arg2, rest) = number(rest)?;
+    let rest = rest.strip_prefix(')')?;
+
+    Some((TokenKind::Mul(arg1, arg2), mem.len() - rest.len()))
+}
+
+/// Most digits of a `mul` argument.
+const MAX_DIGITS: usize = 3;
+
+/// Number made of the 1 to 3 digits at the start of `s` and the rest of it.
+fn number(s: &str) -> Option<(u32, &str)> {
+    let len = s.bytes().take_while(u8::is_ascii_digit).count();
+    if !(1..=MAX_DIGITS).contains(&len) {
+        return None;
+    }
+
+    Some((s[..len].parse().ok()?, &s[len..]))
+}
+
+/// Splits the memory into tokens covering all of it, in order.
+#[derive(Debug, Clone)]
+pub struct Lexer<'a> {
+    mem: &'a str,
+    /// Byte offset of the next token.
+    pos: usize,
+}
+
+impl<'a> Lexer<'a> {
+    pub fn new(mem: &'a str) -> Self {
+        Self { mem, pos: 0 }
+    }
+}
+
+impl Iterator for Lexer<'_> {
+    type Item = Token;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let start = self.pos;
+
+        while let Some(ch) = self.mem[self.pos..].chars().next() {
+            if let Some((kind, len)) = instruction_at(&self.mem[self.pos..]) {
+                if self.pos > start {
+                    // the instruction is left for the next call
+                    break;
+                }
+
+                self.pos += len;
+                return Some(Token {
+                    kind,
+                    span: start..self.pos,
+                });
+            }
+
+            self.pos += ch.len_utf8();
+        }
+
+        (self.pos > start).then_some(Token {
+            kind: TokenKind::Garbage,
+            span: start..self.pos,
+        })
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::lexer::{instruction_at, Lexer, Token, TokenKind};
+
+    #[test]
+    fn instruction_at_works() {
+        assert_eq!(
+            instruction_at("mul(44,46)x"),
+            Some((TokenKind::Mul(44, 46), 10))
+        );
+        assert_eq!(instruction_at("don't()"), Some((TokenKind::Dont, 7)));
+        assert_eq!(instruction_at("do()do()"), Some((TokenKind::Do, 4)));
+        assert_eq!(instruction_at("mul(4*"), None);
+        assert_eq!(instruction_at("mul(6,9!"), None);
+        assert_eq!(instruction_at("mul ( 2 , 4 )"), None);
+        assert_eq!(instruction_at("mul(,4)"), None);
+        assert_eq!(instruction_at("do_not_mul(5,5)"), None);
+
+        assert_eq!(
+            instruction_at("mul(999,0)"),
+            Some((TokenKind::Mul(999, 0), 10))
+        );
+        assert_eq!(instruction_at("mul(1234,5)"), None);
+        assert_eq!(instruction_at("mul(5,1234)"), None);
+        assert_eq!(instruction_at("mul(70000,70000)"), None);
+    }
+
+    #[test]
+    fn lexer_works() {
+        let token = |kind, start, end| Token {
+            kind,
+            span: start..end,
+        };
+
+        assert_eq!(
+            Lexer::new("xmul(2,4)%&mul[3,7]don't()mul(5,5)do()").collect::<Vec<_>>(),
+            vec![
+                token(TokenKind::Garbage, 0, 1),
+                token(TokenKind::Mul(2, 4), 1, 9),
+                token(TokenKind::Garbage, 9, 19),
+                token(TokenKind::Dont, 19, 26),
+                token(TokenKind::Mul(5, 5), 26, 34),
+                token(TokenKind::Do, 34, 38),
+            ]
+        );
+        assert_eq!(
+            Lexer::new("mumul(1,2)é").collect::<Vec<_>>(),
+            vec![
+                token(TokenKind::Garbage, 0, 2),
+                token(TokenKind::Mul(1, 2), 2, 10),
+                token(TokenKind::Garbage, 10, 12),
+            ]
+        );
+        assert_eq!(
+            Lexer::new("xmul(70000,70000)mul(1,2345)").collect::<Vec<_>>(),
+            vec![token(TokenKind::Garbage, 0, 28)]
+        );
+        assert_eq!(Lexer::new("").next(), None);
+    }
+}
@@ -0,0 +1,172 @@
+//! Day 3: Mull It Over.
+
+use std::{fmt, ops::Range};
+
+use aoc_common::{Error, Solution};
+use lexer::{instruction_at, Lexer, TokenKind};
+
+pub mod lexer;
+
+/// Instruction of the corrupted memory.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Instruction {
+    /// Multiplies the arguments.
+    Mul(u32, u32),
+    /// Enables the `mul` instructions that follow.
+    Do,
+    /// Disables the `mul` instructions that follow.
+    Dont,
+}
+
+impl fmt::Display for Instruction {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Instruction::Mul(arg1, arg2) => write!(f, "mul({},{})", arg1, arg2),
+            Instruction::Do => write!(f, "do()"),
+            Instruction::Dont => write!(f, "don't()"),
+        }
+    }
+}
+
+/// Instruction along with the byte offsets of its text in the memory.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Located {
+    pub instruction: Instruction,
+    pub span: Range<usize>,
+}
+
+/// Instructions of the memory in order, the garbage around them left out.
+pub fn instructions(mem: &str) -> Vec<Located> {
+    Lexer::new(mem)
+        .filter_map(|token| {
+            token.kind.instruction().map(|instruction| Located {
+                instruction,
+                span: token.span,
+            })
+        })
+        .collect()
+}
+
+/// Parses `mul(X,Y)` instruction at the start of `mem` and returns the product, or `None` if
+/// the memory does not start with a valid instruction.
+pub fn try_parse_and_mul(mem: &str) -> Option<u32> {
+    match instruction_at(mem)? {
+        (TokenKind::Mul(arg1, arg2), _) => Some(arg1 * arg2),
+        _ => None,
+    }
+}
+
+/// Sums products of all `mul` instructions. If `conditional` is set, only the products enabled
+/// by `do()`/`don't()` instructions are summed up.
+pub fn sum_products(instructions: &[Located], conditional: bool) -> aoc_common::Result<u32> {
+    let overflow = || Error::Overflow {
+        quantity: "sum of products",
+        width: "u32",
+    };
+
+    let mut sum_products: u32 = 0;
+    let mut enable_multiplication = true;
+    for located in instructions {
+        match located.instruction {
+            Instruction::Mul(arg1, arg2) if enable_multiplication || !conditional => {
+                let product = arg1.checked_mul(arg2).ok_or_else(overflow)?;
+                sum_products = sum_products.checked_add(product).ok_or_else(overflow)?;
+            }
+            Instruction::Mul(..) => {}
+            Instruction::Do => enable_multiplication = true,
+            Instruction::Dont => enable_multiplication = false,
+        }
+    }
+
+    Ok(sum_products)
+}
+
+pub struct Day3;
+
+impl Solution for Day3 {
+    const DAY: u8 = 3;
+    const PART1: &'static str = "Sum of all products";
+    const PART2: &'static str = "Sum of enabled products";
+
+    type Input = Vec<Located>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        Ok(instructions(input))
+    }
+
+    /// Sum of all products.
+    fn part1(instructions: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        sum_products(instructions, false)
+    }
+
+    /// Sum of enabled products.
+    fn part2(instructions: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        sum_products(instructions, true)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::Error;
+
+    use crate::{instructions, sum_products, try_parse_and_mul, Instruction, Located};
+
+    #[test]
+    fn try_parse_mul_works() {
+        assert!(try_parse_and_mul("mul(771,307(").is_none());
+        assert!(matches!(try_parse_and_mul("mul(301,529)who(86,180)"), Some(p) if p == 301 * 529));
+    }
+
+    #[test]
+    fn sum_products_rejects_overflow() {
+        let mem = "mul(999,999)".repeat(5000);
+
+        assert!(matches!(
+            sum_products(&instructions(&mem), false),
+            Err(Error::Overflow { .. })
+        ));
+        assert_eq!(
+            sum_products(&instructions("mul(70000,70000)mul(2,3)"), false).unwrap(),
+            6
+        );
+    }
+
+    #[test]
+    fn instructions_work() {
+        let mem = "xmul(2,4)&mul[3,7]!^don't()_mul(5,5)+mul(32,64](mul(11,8)undo()?mul(8,5))";
+        let located = instructions(mem);
+
+        assert_eq!(
+            located[..2],
+            [
+                Located {
+                    instruction: Instruction::Mul(2, 4),
+                    span: 1..9
+                },
+                Located {
+                    instruction: Instruction::Dont,
+                    span: 20..27
+                }
+            ]
+        );
+        assert_eq!(
+            located
+                .iter()
+                .map(|l| l.instruction.to_string())
+                .collect::<Vec<_>>(),
+            [
+                "mul(2,4)",
+                "don't()",
+                "mul(5,5)",
+                "mul(11,8)",
+                "do()",
+                "mul(8,5)"
+            ]
+        );
+        for l in &located {
+            assert_eq!(mem[l.span.clone()], l.instruction.to_string());
+        }
+    }
+}
@@ -0,0 +1,16 @@
+use aoc_common::Solution;
+use day3::Day3;
+
+#[test]
+fn part1_example() {
+    let mem = Day3::parse(include_str!("fixtures/example1.txt")).unwrap();
+
+    assert_eq!(Day3::part1(&mem).unwrap(), 161);
+}
+
+#[test]
+fn part2_example() {
+    let mem = Day3::parse(include_str!("fixtures/example2.txt")).unwrap();
+
+    assert_eq!(Day3::part2(&mem).unwrap(), 48);
+}
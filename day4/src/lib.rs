@@ -0,0 +1,274 @@
+//! Day 4: Ceres Search.
+
+use aoc_common::{
+    grid::{self, Grid},
+    Solution,
+};
+
+fn check_if_word_in_direction(
+    word: &str,
+    map: &[Vec<char>],
+    r: usize,
+    c: usize,
+    direction: &(isize, isize),
+) -> bool {
+    let mut cr = r as isize;
+    let mut cc = c as isize;
+    let mut buf = String::new();
+
+    loop {
+        if cr < 0 || cr as usize >= map.len() {
+            break;
+        }
+
+        if cc < 0 || cc as usize >= map[cr as usize].len() {
+            break;
+        }
+
+        buf.push(map[cr as usize][cc as usize]);
+
+        if buf.len() == word.len() {
+            break;
+        }
+
+        cr += direction.0;
+        cc += direction.1;
+    }
+
+    buf == word
+}
+
+/// Counts X-MAS shapes (two diagonal MAS crossing) centered at the `A` at `(r, c)`.
+pub fn count_x_max_at_position(map: &[Vec<char>], r: usize, c: usize) -> usize {
+    let rr = r as isize;
+    let cc = c as isize;
+    let combos = &[
+        [
+            ((rr - 1, cc - 1), (1, 1)),
+            ((rr + 1, cc + 1), (-1, -1)),
+            ((rr - 1, cc + 1), (1, -1)),
+            ((rr + 1, cc - 1), (-1, 1)),
+        ],
+        // [
+        //     ((rr, cc - 1), (0, 1)),
+        //     ((rr, cc + 1), (0, -1)),
+        //     ((rr - 1, cc), (1, 0)),
+        //     ((rr + 1, cc), (-1, 0)),
+        // ],
+    ];
+
+    combos
+        .iter()
+        .map(|x| {
+            (x.map(|((sr, sc), direction)| {
+                check_if_word_in_direction("MAS", map, sr as usize, sc as usize, &direction)
+                    as usize
+            })
+            .iter()
+            .sum::<usize>()
+                > 1) as usize
+        })
+        .sum()
+}
+
+/// Counts XMAS words in all 8 directions starting at the `X` at `(r, c)`.
+pub fn xmases_at_point(map: &[Vec<char>], r: usize, c: usize) -> usize {
+    let directions: &[(isize, isize)] = &[
+        /* right */ (1, 0),
+        /* left */ (-1, 0),
+        /* up */ (0, -1),
+        /* down */ (0, 1),
+        /* diag/up-right */ (1, 1),
+        /* diag/up-left */ (-1, 1),
+        /* diag/down-left */ (-1, -1),
+        /* diag/down-right */ (1, -1),
+    ];
+
+    let mut sum = 0;
+
+    for direction in directions {
+        sum += check_if_word_in_direction("XMAS", map, r, c, direction) as usize;
+    }
+
+    sum
+}
+
+fn count_at_positions(
+    map: &[Vec<char>],
+    letter: char,
+    count_at_position: fn(&[Vec<char>], usize, usize) -> usize,
+) -> usize {
+    let mut total = 0;
+    for r in 0..map.len() {
+        for c in 0..map[r].len() {
+            if map[r][c] == letter {
+                total += count_at_position(map, r, c);
+            }
+        }
+    }
+
+    total
+}
+
+/// Counts both XMAS words and X-MAS shapes in the whole word search.
+pub fn calc_xmas_puzzle(map: &[Vec<char>]) -> (usize, usize) {
+    (
+        count_at_positions(map, 'X', xmases_at_point),
+        count_at_positions(map, 'A', count_x_max_at_position),
+    )
+}
+
+pub struct Day4;
+
+impl Solution for Day4 {
+    const DAY: u8 = 4;
+    const PART1: &'static str = "Total XMAS encounters found";
+    const PART2: &'static str = "Total X-MAS encounters found";
+
+    type Input = Grid;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        Ok(grid::parse_grid(input))
+    }
+
+    /// Total XMAS encounters.
+    fn part1(map: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        Ok(count_at_positions(map, 'X', xmases_at_point))
+    }
+
+    /// Total X-MAS encounters.
+    fn part2(map: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        Ok(count_at_positions(map, 'A', count_x_max_at_position))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::calc_xmas_puzzle;
+
+    #[test]
+    fn calc_xmas_puzzle_works1() {
+        let input = vec![
+            vec!['.', 'M', '.', 'S', '.', '.', '.', '.', '.', '.'],
+            vec!['.', '.', 'A', '.', '.', 'M', 'S', 'M', 'S', '.'],
+            vec!['.', 'M', '.', 'S', '.', 'M', 'A', 'A', '.', '.'],
+            vec!['.', '.', 'A', '.', 'A', 'S', 'M', 'S', 'M', '.'],
+            vec!['.', 'M', '.', 'S', '.', 'M', '.', '.', '.', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+            vec!['S', '.', 'S', '.', 'S', '.', 'S', '.', 'S', '.'],
+            vec!['.', 'A', '.', 'A', '.', 'A', '.', 'A', '.', '.'],
+            vec!['M', '.', 'M', '.', 'M', '.', 'M', '.', 'M', '.'],
+            vec!['.', '.', '.', '.', '.', '.', '.', '.', '.', '.'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 9));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works2() {
+        let input = vec![
+            vec!['.', '.', '.', '.', 'X', 'X', 'M', 'A', 'S', '.'],
+            vec!['.', 'S', 'A', 'M', 'X', 'M', 'S', '.', '.', '.'],
+            vec!['.', '.', '.', 'S', '.', '.', 'A', '.', '.', '.'],
+            vec!['.', '.', 'A', '.', 'A', '.', 'M', 'S', '.', 'X'],
+            vec!['X', 'M', 'A', 'S', 'A', 'M', 'X', '.', 'M', 'M'],
+            vec!['X', '.', '.', '.', '.', '.', 'X', 'A', '.', 'A'],
+            vec!['S', '.', 'S', '.', 'S', '.', 'S', '.', 'S', 'S'],
+            vec!['.', 'A', '.', 'A', '.', 'A', '.', 'A', '.', 'A'],
+            vec!['.', '.', 'M', '.', 'M', '.', 'M', '.', 'M', 'M'],
+            vec!['.', 'X', '.', 'X', '.', 'X', 'M', 'A', 'S', 'X'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (18, 3));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works3() {
+        let input = vec![
+            vec!['.', 'M', '.'],
+            vec!['S', 'A', 'M'],
+            vec!['.', 'S', '.'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 0));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works4() {
+        let input = vec![
+            vec!['M', 'M', 'S'],
+            vec!['S', 'A', 'M'],
+            vec!['M', 'S', 'S'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 1));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works5() {
+        let input = vec![
+            vec!['M', '.', 'S'],
+            vec!['.', 'A', '.'],
+            vec!['M', '.', 'S'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 1));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works6() {
+        let input = vec![
+            vec!['M', '.', 'M'],
+            vec!['.', 'A', '.'],
+            vec!['M', '.', 'S'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 0));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works7() {
+        let input = vec![
+            vec!['M', 'M', '.'],
+            vec!['.', 'A', '.'],
+            vec!['.', 'S', 'S'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 0));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works8() {
+        let input = vec![
+            vec!['S', 'M', 'S'],
+            vec!['.', 'A', '.'],
+            vec!['M', 'S', 'M'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 1));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works9() {
+        let input = vec![
+            vec!['S', 'M', 'S'],
+            vec!['A', 'A', 'A'],
+            vec!['M', 'S', 'M'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 1));
+    }
+
+    #[test]
+    fn calc_xmas_puzzle_works10() {
+        let input = vec![
+            vec!['M', 'S', 'M', 'S'],
+            vec!['M', 'A', 'A', '.'],
+            vec!['S', 'M', 'S', 'M'],
+        ];
+
+        assert_eq!(calc_xmas_puzzle(&input), (0, 2));
+    }
+}
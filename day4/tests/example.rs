@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day4::Day4;
+
+const EXAMPLE: &str = include_str!("fixtures/example.txt");
+
+#[test]
+fn part1_example() {
+    let map = Day4::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day4::part1(&map).unwrap(), 18);
+}
+
+#[test]
+fn part2_example() {
+    let map = Day4::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day4::part2(&map).unwrap(), 9);
+}
@@ -0,0 +1,159 @@
+//! Day 5: Print Queue.
+
+use aoc_common::{parse, Error, Solution};
+
+/// Page ordering rule `X|Y`: page `X` must be printed before page `Y` if both are in the update.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Rule(u32, u32);
+
+impl Rule {
+    pub fn new(first: u32, second: u32) -> Self {
+        Self(first, second)
+    }
+
+    /// Parses the rule from `X|Y` found on the given (1-based) `line_no`.
+    pub fn parse(input: &str, line_no: usize) -> aoc_common::Result<Self> {
+        let [first, second] = parse::exactly(
+            parse::separated_tokens(input, '|'),
+            input,
+            line_no,
+            "page number",
+        )?;
+
+        Ok(Self::new(first, second))
+    }
+
+    /// Checks whether the update does not violate the rule.
+    pub fn is_satisfied_for_update(&self, update: &[u32]) -> bool {
+        let first_pos = update.iter().position(|el| *el == self.0);
+        let second_pos = update.iter().position(|el| *el == self.1);
+
+        !matches!((first_pos, second_pos), (Some(f), Some(s)) if f > s)
+    }
+
+    ///
+    /// Applies current rule to the passed update, as necessary.
+    ///
+    /// Returns:
+    ///  - true if update violated the rule
+    ///  - false if rule was already satisfied and no action was necessary
+    pub fn satisfy_for_update(&self, update: &mut [u32]) -> bool {
+        let first_pos = update.iter().position(|el| *el == self.0);
+        let second_pos = update.iter().position(|el| *el == self.1);
+
+        if let (Some(f), Some(s)) = (first_pos, second_pos) {
+            if f > s {
+                // println!("Rule {}|{} not satisfied for update: {:?}", self.0, self.1, update);
+                // if order is wrong, swap
+                update.swap(first_pos.unwrap(), second_pos.unwrap());
+                // println!("                     After swapping: {:?}", update);
+
+                return true;
+            }
+        }
+
+        false
+    }
+}
+
+/// Checks whether the update satisfies all the rules.
+pub fn is_correctly_ordered(rules: &[Rule], update: &[u32]) -> bool {
+    rules.iter().all(|r| r.is_satisfied_for_update(update))
+}
+
+/// Reorders the update until it satisfies all the rules.
+///
+/// Returns whether the update had to be reordered.
+pub fn oder_update(rules: &[Rule], update: &mut [u32]) -> bool {
+    let mut required_reordering = false;
+    loop {
+        let mut ordered = true;
+        for rule in rules {
+            ordered &= !rule.satisfy_for_update(update);
+            if !ordered {
+                required_reordering = true;
+            }
+        }
+
+        if ordered {
+            break;
+        }
+    }
+
+    required_reordering
+}
+
+/// Page ordering rules followed by the updates to be printed.
+pub struct PrintQueue {
+    pub rules: Vec<Rule>,
+    pub updates: Vec<Vec<u32>>,
+}
+
+pub struct Day5;
+
+impl Solution for Day5 {
+    const DAY: u8 = 5;
+    const PART1: &'static str = "Sum of middle page numbers of correctly ordered updates";
+    const PART2: &'static str =
+        "Sum of middle page numbers of incorrectly ordered updates (after fixing)";
+
+    type Input = PrintQueue;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        let mut rules = Vec::new();
+        let mut updates = Vec::new();
+        let mut header_read = true;
+        for (idx, line) in input.lines().enumerate() {
+            let line_no = idx + 1;
+            if header_read {
+                if line.is_empty() {
+                    header_read = false;
+                    continue;
+                }
+
+                let rule = Rule::parse(line, line_no)?;
+
+                rules.push(rule);
+            } else {
+                let update = parse::separated::<u32>(line, line_no, ',')?;
+
+                if update.len() % 2 == 0 {
+                    return Err(Error::Invalid {
+                        line: line_no,
+                        column: 1,
+                        message: "update has even number of pages, can't determine the middle page number".to_string(),
+                    });
+                }
+
+                updates.push(update);
+            }
+        }
+
+        Ok(PrintQueue { rules, updates })
+    }
+
+    /// Sum of middle page numbers of correctly ordered updates.
+    fn part1(queue: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        Ok(queue
+            .updates
+            .iter()
+            .filter(|update| is_correctly_ordered(&queue.rules, update))
+            .map(|update| update[update.len() / 2])
+            .sum())
+    }
+
+    /// Sum of middle page numbers of incorrectly ordered updates, after fixing their order.
+    fn part2(queue: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        let mut incorrectly_ordered_update_middle_pages_sum = 0;
+        for update in &queue.updates {
+            let mut update = update.clone();
+            if oder_update(&queue.rules, &mut update) {
+                incorrectly_ordered_update_middle_pages_sum += update[update.len() / 2];
+            }
+        }
+
+        Ok(incorrectly_ordered_update_middle_pages_sum)
+    }
+}
@@ -0,0 +1,18 @@
+use aoc_common::Solution;
+use day5::Day5;
+
+const EXAMPLE: &str = include_str!("fixtures/example.txt");
+
+#[test]
+fn part1_example() {
+    let queue = Day5::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day5::part1(&queue).unwrap(), 143);
+}
+
+#[test]
+fn part2_example() {
+    let queue = Day5::parse(EXAMPLE).unwrap();
+
+    assert_eq!(Day5::part2(&queue).unwrap(), 123);
+}
@@ -0,0 +1,41 @@
+use day5::{is_correctly_ordered, oder_update, Rule};
+
+fn rules() -> Vec<Rule> {
+    [
+        (47, 53),
+        (97, 61),
+        (97, 47),
+        (75, 53),
+        (61, 53),
+        (97, 53),
+        (47, 61),
+        (75, 47),
+        (75, 61),
+    ]
+    .into_iter()
+    .map(|(first, second)| Rule::new(first, second))
+    .collect()
+}
+
+#[test]
+fn rule_parse_works() {
+    assert_eq!(Rule::parse("47|53", 1).unwrap(), Rule::new(47, 53));
+    assert!(Rule::parse("47|", 1).is_err());
+    assert!(Rule::parse("47|53|61", 1).is_err());
+}
+
+#[test]
+fn is_correctly_ordered_works() {
+    assert!(is_correctly_ordered(&rules(), &[75, 47, 61, 53]));
+    assert!(!is_correctly_ordered(&rules(), &[61, 97, 53, 47]));
+}
+
+#[test]
+fn oder_update_works() {
+    let mut update = vec![97, 75, 47, 61, 53];
+    assert!(!oder_update(&rules(), &mut update));
+
+    let mut update = vec![61, 97, 53, 47];
+    assert!(oder_update(&rules(), &mut update));
+    assert_eq!(update, vec![97, 47, 61, 53]);
+}
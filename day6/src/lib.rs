@@ -0,0 +1,460 @@
+//! Day 6: Guard Gallivant.
+
+use aoc_common::{grid, Error, Solution};
+
+/// Reads the lab map, returning it along with the guard location, `(-1, -1)` if there is no guard.
+pub fn read_map_from_str(s: &str) -> (Vec<Vec<char>>, (isize, isize)) {
+    let map = grid::parse_grid(s);
+    let (r, c) = grid::find(&map, |ch| ['^', '>', 'v', '<'].contains(&ch))
+        .map_or((-1, -1), |(r, c)| (r as isize, c as isize));
+
+    (map, (r, c))
+}
+
+#[allow(unused)]
+fn print_map(map: &[Vec<char>]) {
+    map.iter().for_each(|row| {
+        println!("{}", row.iter().collect::<String>());
+    });
+}
+
+fn next_step(map: &[Vec<char>], location: (usize, usize)) -> (isize, isize) {
+    let (r, c) = location;
+    let dr: isize = match map[r][c] {
+        '^' => -1,
+        'v' => 1,
+        _ => 0,
+    };
+
+    let dc: isize = match map[r][c] {
+        '<' => -1,
+        '>' => 1,
+        _ => 0,
+    };
+
+    let tr = r as isize + dr;
+    let tc = c as isize + dc;
+
+    (tr, tc)
+}
+
+fn step_guard(map: &mut [Vec<char>], from: (usize, usize), to: (usize, usize)) -> bool {
+    let (r, c) = from;
+    let (tr, tc) = to;
+
+    let unvisited = map[tr][tc] == '.';
+    map[tr][tc] = map[r][c];
+    map[r][c] = 'X';
+
+    // println!(
+    //     "Guard moved {} to ({}, {}) [{}]",
+    //     map[tr][tc],
+    //     tr,
+    //     tc,
+    //     if unvisited { "new" } else { "has been before" }
+    // );
+
+    unvisited
+}
+
+fn turn_guard_right(map: &mut [Vec<char>], location: (usize, usize)) {
+    let (r, c) = location;
+
+    map[r][c] = match map[r][c] {
+        '^' => '>',
+        '>' => 'v',
+        'v' => '<',
+        '<' => '^',
+        _ => panic!("Guard is not at ({}, {})!", r, c),
+    };
+
+    // println!("Guard turned right: {} at ({}, {})", map[r][c], r, c);
+}
+
+fn within_lab(map: &[Vec<char>], location: (isize, isize)) -> bool {
+    let (tr, tc) = location;
+    tr >= 0 && tr < map.len() as isize && tc >= 0 && tc < map[tr as usize].len() as isize
+}
+
+fn can_go(map: &[Vec<char>], location: (usize, usize)) -> bool {
+    let (r, c) = location;
+
+    map[r][c] != '#'
+}
+
+/// Walks the guard from `location` until it leaves the lab, marking visited positions with `X`.
+/// Before every move or turn, `on_step` is called with the map and the guard location.
+///
+/// Returns the number of distinct visited positions.
+fn walk_guard(
+    map: &mut [Vec<char>],
+    location: (usize, usize),
+    mut on_step: impl FnMut(&[Vec<char>], (usize, usize)),
+) -> usize {
+    let mut r: isize = location.0 as isize;
+    let mut c: isize = location.1 as isize;
+
+    let mut positions_count = 1;
+    loop {
+        let (nr, nc) = next_step(map, (r as usize, c as usize));
+
+        if !within_lab(map, (nr, nc)) {
+            break;
+        }
+
+        on_step(map, (r as usize, c as usize));
+
+        if !can_go(map, (nr as usize, nc as usize)) {
+            turn_guard_right(map, (r as usize, c as usize));
+            continue;
+        }
+
+        if step_guard(map, (r as usize, c as usize), (nr as usize, nc as usize)) {
+            positions_count += 1;
+        }
+        (r, c) = (nr, nc);
+
+        // print_map(map);
+    }
+
+    positions_count
+}
+
+/// Walks the guard out of the lab without looking for loops.
+///
+/// Returns the number of distinct visited positions.
+pub fn count_visited_positions(map: &mut [Vec<char>], location: (usize, usize)) -> usize {
+    walk_guard(map, location, |_, _| {})
+}
+
+/// Same walk as [`run_guard_till_exit`], for when only the loops matter.
+///
+/// Returns the number of loopable perimeters met.
+pub fn count_loopable_perimeters(map: &mut [Vec<char>], location: (usize, usize)) -> usize {
+    run_guard_till_exit(map, location).1
+}
+
+/// Walks the guard out of the lab, checking every step with [`is_loopable_perimeter`].
+///
+/// Returns the number of distinct visited positions and the number of loopable perimeters met.
+pub fn run_guard_till_exit(map: &mut [Vec<char>], location: (usize, usize)) -> (usize, usize) {
+    let mut loops_count = 0;
+    let positions_count = walk_guard(map, location, |map, location| {
+        if is_loopable_perimeter(map, location) {
+            // println!("Found loopable perimeter at ({}, {}).", location.0, location.1);
+            loops_count += 1;
+        }
+    });
+
+    (positions_count, loops_count)
+}
+
+/// Checks whether turning right at `location` would bring the guard back to it, i.e. placing an
+/// obstacle in front of the guard creates a loop.
+pub fn is_loopable_perimeter(map: &[Vec<char>], location: (usize, usize)) -> bool {
+    let (sr, sc) = location;
+
+    let mut turns = 0;
+
+    // presume that current direction is caused by turn to avoid obstacle
+    let mut direction = match map[sr][sc] {
+        '^' => '<',
+        '>' => '^',
+        'v' => '>',
+        '<' => 'v',
+        _ => panic!("Wrong direction: {}", map[sr][sc]),
+    };
+
+    let mut r = sr;
+    let mut c = sc;
+    while turns <= 4 {
+        direction = match direction {
+            '^' => '>',
+            '>' => 'v',
+            'v' => '<',
+            '<' => '^',
+            _ => panic!("Wrong direction: {}", direction),
+        };
+        turns += 1;
+
+        let (dr, dc) = match direction {
+            '^' => (-1_isize, 0),
+            '>' => (0, 1),
+            'v' => (1, 0),
+            '<' => (0, -1_isize),
+            _ => panic!("Wrong direction: {}", direction),
+        };
+
+        loop {
+            let (nr, nc) = (r as isize + dr, c as isize + dc);
+
+            if !within_lab(map, (nr, nc)) {
+                return false;
+            }
+
+            if !can_go(map, (nr as usize, nc as usize)) {
+                break;
+            }
+
+            r = nr as usize;
+            c = nc as usize;
+
+            if r == sr && c == sc {
+                return true;
+            }
+        }
+    }
+
+    r == sr && c == sc
+}
+
+/// Lab map with the guard at the starting position.
+#[derive(Clone)]
+pub struct Lab {
+    pub map: Vec<Vec<char>>,
+    pub guard: (usize, usize),
+}
+
+pub struct Day6;
+
+impl Solution for Day6 {
+    const DAY: u8 = 6;
+    const PART1: &'static str = "Positions count";
+    const PART2: &'static str = "Possible loops count";
+
+    type Input = Lab;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> aoc_common::Result<Self::Input> {
+        let (map, (r, c)) = read_map_from_str(input);
+
+        for (r, row) in map.iter().enumerate() {
+            if let Some(c) = row.iter().position(|ch| !".#^>v<".contains(*ch)) {
+                return Err(Error::UnexpectedToken {
+                    line: r + 1,
+                    column: c + 1,
+                    token: row[c].to_string(),
+                    expected: "'.', '#' or guard",
+                });
+            }
+        }
+
+        if r < 0 || c < 0 {
+            return Err(Error::Puzzle(
+                "guard is expected to be within the lab".to_string(),
+            ));
+        }
+
+        Ok(Lab {
+            map,
+            guard: (r as usize, c as usize),
+        })
+    }
+
+    /// Number of distinct positions visited by the guard.
+    fn part1(lab: &Self::Input) -> aoc_common::Result<Self::Answer1> {
+        let Lab { mut map, guard } = lab.clone();
+        Ok(count_visited_positions(&mut map, guard))
+    }
+
+    /// Number of possible loops.
+    fn part2(lab: &Self::Input) -> aoc_common::Result<Self::Answer2> {
+        let Lab { mut map, guard } = lab.clone();
+        Ok(count_loopable_perimeters(&mut map, guard))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use aoc_common::{Error, Solution};
+
+    use crate::{read_map_from_str, run_guard_till_exit, Day6};
+
+    #[test]
+    fn parse_rejects_malformed_map() {
+        assert!(matches!(
+            Day6::parse("....\n.#x^\n"),
+            Err(Error::UnexpectedToken {
+                line: 2,
+                column: 3,
+                ..
+            })
+        ));
+        assert!(matches!(Day6::parse("....\n.#..\n"), Err(Error::Puzzle(_))));
+    }
+
+    #[test]
+    fn read_map_from_str_works() {
+        let (map, (r, c)) = read_map_from_str(
+            "....#.....\n\
+            .........#\n\
+            ..........\n\
+            ..#.......\n\
+            .......#..\n\
+            ..........\n\
+            .#..^.....\n\
+            ........#.\n\
+            #.........\n\
+            ......#...",
+        );
+
+        assert_eq!(map.len(), 10);
+        assert_eq!(map[0].len(), 10);
+        assert_eq!((r, c), (6, 4));
+    }
+
+    #[test]
+    fn run_guard_till_exit_works() {
+        let (mut map, (r, c)) = read_map_from_str(
+            "....#.....\n\
+            .........#\n\
+            ..........\n\
+            ..#.......\n\
+            .......#..\n\
+            ..........\n\
+            .#..^.....\n\
+            ........#.\n\
+            #.........\n\
+            ......#...",
+        );
+
+        let (positions_count, loops_count) =
+            run_guard_till_exit(&mut map, (r as usize, c as usize));
+
+        assert_eq!(positions_count, 41);
+        assert_eq!(loops_count, 6);
+    }
+
+    mod is_loopable_perimeter {
+        use crate::{is_loopable_perimeter, read_map_from_str};
+
+        #[test]
+        fn if_hit_lab_wall_then_false() {
+            let (map, (r, c)) = read_map_from_str(
+                ".....\n\
+                #<....",
+            );
+
+            assert!(!is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn if_ran_out_of_turns_then_false() {
+            let (map, (r, c)) = read_map_from_str(
+                ".#...\n\
+                 ...#.\n\
+                 #<...\n\
+                 #....\n\
+                 ..#...",
+            );
+
+            assert!(!is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn if_loop_then_true() {
+            let (map, (r, c)) = read_map_from_str(
+                ".#...\n\
+                 ...#.\n\
+                 #<...\n\
+                 ..#..",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn if_inplace_turns_then_true() {
+            let (map, (r, c)) = read_map_from_str(
+                ".#...\n\
+                 #<#..\n\
+                 .#...",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+        #[test]
+        fn if_back_and_force_then_true() {
+            let (map, (r, c)) = read_map_from_str(
+                ".#...\n\
+                 #<..#\n\
+                 ...#.",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn sample_6_4_map_true() {
+            let (map, (r, c)) = read_map_from_str(
+                "....#.....\n\
+                 .........#\n\
+                 ..........\n\
+                 ..#.......\n\
+                 .......#..\n\
+                 ..........\n\
+                 .#..^.....\n\
+                 ........#.\n\
+                 #.........\n\
+                 ......#...",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn sample_6_6_map_true() {
+            let (map, (r, c)) = read_map_from_str(
+                "....#.....\n\
+                 .........#\n\
+                 ..........\n\
+                 ..#.......\n\
+                 .......#..\n\
+                 ..........\n\
+                 .#....<...\n\
+                 ........#.\n\
+                 #.........\n\
+                 ......#...",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn sample_7_6_map_true() {
+            let (map, (r, c)) = read_map_from_str(
+                "....#.....\n\
+                 .........#\n\
+                 ..........\n\
+                 ..#.......\n\
+                 .......#..\n\
+                 ..........\n\
+                 .#........\n\
+                 ......v.#.\n\
+                 #.........\n\
+                 ......#...",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+
+        #[test]
+        fn sample_8_2_map_true() {
+            let (map, (r, c)) = read_map_from_str(
+                "....#.....\n\
+                 .........#\n\
+                 ..........\n\
+                 ..#.......\n\
+                 .......#..\n\
+                 ..........\n\
+                 .#........\n\
+                 ........#.\n\
+                 #.<.......\n\
+                 ......#...",
+            );
+
+            assert!(is_loopable_perimeter(&map, (r as usize, c as usize)));
+        }
+    }
+}